@@ -0,0 +1,28 @@
+use terminal_style::color::{hsl_to_rgb, Color};
+use terminal_style::format::{join_horizontal, Canvas, CanvasMode, VAlign};
+
+fn main() {
+    println!("=== Canvas Demo ===\n");
+
+    // A sine wave plotted with braille dots, colored along its length
+    let mut plot = Canvas::new(40, 8, CanvasMode::Braille);
+    let (width, height) = (plot.width() as i32, plot.height() as i32);
+    let y_at = |x: i32| {
+        let phase = x as f64 / width as f64 * std::f64::consts::TAU * 2.0;
+        ((1.0 - phase.sin()) / 2.0 * (height - 1) as f64).round() as i32
+    };
+    for x in 0..width - 1 {
+        let color = Color::Rgb(hsl_to_rgb(x as f64 * 3.0, 1.0, 0.6));
+        plot.line(x, y_at(x), x + 1, y_at(x + 1), color);
+    }
+    plot.rect(0, 0, width as u32, height as u32, Color::Ansi256(240));
+
+    // Shapes drawn with half blocks, which keep a color per pixel
+    let mut shapes = Canvas::new(16, 8, CanvasMode::HalfBlock);
+    shapes.fill_rect(2, 2, 6, 6, Color::Rgb([255, 99, 71]));
+    shapes.circle(10, 9, 5, Color::Rgb([30, 144, 255]));
+
+    for line in join_horizontal(&[plot.render(), shapes.render()], VAlign::Middle) {
+        println!("{}  ", line);
+    }
+}
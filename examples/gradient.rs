@@ -1,4 +1,4 @@
-use terminal_style::format::background_rgb;
+use terminal_style::format::{background_rgb, render_grid};
 
 fn main() -> Result<(), terminal_style::color::ColorConversionError> {
     println!("=== 24-bit TrueColor Gradient Demo ===\n");
@@ -6,7 +6,9 @@ fn main() -> Result<(), terminal_style::color::ColorConversionError> {
     let width = 60;
     let height = 10;
 
+    let mut grid = Vec::with_capacity(height);
     for y in 0..height {
+        let mut row = Vec::with_capacity(width);
         for x in 0..width {
             // Calculate RGB values for a smooth horizontal gradient
             // From Blue (left) to Red (right)
@@ -15,11 +17,14 @@ fn main() -> Result<(), terminal_style::color::ColorConversionError> {
             let b = 255 - r;
 
             // Using background_rgb for "pixels"
-            print!("{}", background_rgb([r, g, b], " ")?);
+            row.push(background_rgb([r, g, b], " ")?);
         }
-        println!();
+        grid.push(row);
     }
 
+    // Only the colors that change between neighboring pixels are emitted
+    println!("{}", render_grid(&grid));
+
     println!("\nThis gradient uses 24-bit TrueColor sequences (\\x1b[48;2;R;G;Bm).");
     println!("If you see smooth transitions, your terminal supports TrueColor.");
 
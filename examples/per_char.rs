@@ -0,0 +1,42 @@
+use terminal_style::color::Color;
+use terminal_style::format::{bold, hue_cycle, per_char, rainbow, Style};
+
+fn main() {
+    println!("=== Per-Character Styling Demo ===\n");
+
+    println!("{}", rainbow("Every character gets its own hue"));
+    println!(
+        "{}",
+        hue_cycle("Stepping 15 degrees per character", 200.0, 15.0)
+    );
+
+    // The closure picks a style from the index and the character itself
+    let zebra = per_char("Alternating bold and plain", |i, _| {
+        if i % 2 == 0 {
+            Style::new().bold()
+        } else {
+            Style::new()
+        }
+    });
+    println!("{}", zebra);
+
+    let vowels = per_char("Vowels in color, consonants as they are", |_, c| {
+        if "aeiouAEIOU".contains(c) {
+            Style::new().fg(Color::Ansi256(208))
+        } else {
+            Style::new()
+        }
+    });
+    println!("{}", vowels);
+
+    // Existing styling is kept, with the new style layered on top
+    let slashes: String = (0..40)
+        .map(|i| if i % 3 == 0 { '╲' } else { '╱' })
+        .collect();
+    let shaded = per_char(bold(slashes), |i, _| {
+        Style::new().fg(Color::Ansi256(16 + (i * 6 % 216) as u8))
+    });
+    println!("{}", shaded);
+
+    println!("\nNeighboring characters with the same style share one escape sequence.");
+}
@@ -0,0 +1,25 @@
+use std::io::stdout;
+use std::{thread, time::Duration};
+use terminal_style::format::{redraw, ColorCycle, Frames, ProgressBar, Spinner, Style, Ticker};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let ticker = Ticker::new(Duration::from_millis(80));
+    let spinner = Spinner::new(Frames::DOTS)
+        .style(Style::new().bold())
+        .colors(ColorCycle::pulse("#1E90FF", 25)?);
+    let bar = ProgressBar::new(30).fill([30, 144, 255]).percentage(true);
+
+    let steps = 150;
+    for step in 0..=steps {
+        let line = format!(
+            "{} {}",
+            spinner.frame(ticker.tick()),
+            bar.render(step as f64 / steps as f64)
+        );
+        redraw(&mut stdout(), &line)?;
+        thread::sleep(Duration::from_millis(20));
+    }
+    println!();
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use std::io::{self, Write};
-use terminal_style::color::{hex_to_ansi8, validate_ansi, validate_hex, ColorConversionError};
+use terminal_style::color::{try_hex_to_ansi8, validate_ansi, ColorConversionError};
 use terminal_style::format::color;
 
 fn main() -> Result<(), ColorConversionError> {
@@ -56,11 +56,8 @@ fn main() -> Result<(), ColorConversionError> {
                     break;
                 }
 
-                match validate_hex(input) {
-                    Ok(()) => {
-                        let ansi_code = hex_to_ansi8(input);
-                        println!("{}", color(ansi_code, "Hello World")?);
-                    }
+                match try_hex_to_ansi8(input) {
+                    Ok(ansi_code) => println!("{}", color(ansi_code, "Hello World")?),
                     Err(err) => eprintln!("❌ {}", err),
                 }
             }
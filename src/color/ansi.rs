@@ -1,9 +1,4 @@
-use super::{
-    error::ColorConversionError,
-    hex::hex_to_rgb,
-    rgb::rgb_to_hex,
-    validate::validate_hex,
-};
+use super::{error::ColorConversionError, hex::try_hex_to_rgb, rgb::rgb_to_hex};
 
 /// Converts various types of color representations into an RGB color array.
 ///
@@ -53,8 +48,7 @@ impl IntoRgb for [u8; 3] {
 /// Validates the hex format before conversion.
 impl IntoRgb for String {
     fn into_rgb(self) -> Result<[u8; 3], ColorConversionError> {
-        validate_hex(&self)?;
-        Ok(hex_to_rgb(&self))
+        try_hex_to_rgb(&self)
     }
 }
 
@@ -62,8 +56,7 @@ impl IntoRgb for String {
 /// Validates the hex format before conversion.
 impl IntoRgb for &str {
     fn into_rgb(self) -> Result<[u8; 3], ColorConversionError> {
-        validate_hex(self)?;
-        Ok(hex_to_rgb(self))
+        try_hex_to_rgb(self)
     }
 }
 
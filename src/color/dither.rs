@@ -0,0 +1,220 @@
+use super::{
+    error::ColorConversionError,
+    palette::{srgb_to_linear, Palette},
+};
+
+/// How [`dither`] spreads the difference between the original colors and
+/// the palette colors they are mapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Dither {
+    /// Map each pixel to its nearest palette color. Smooth gradients band.
+    #[default]
+    None,
+    /// Floyd–Steinberg error diffusion: all of each pixel's error is passed
+    /// on to its neighbors to the right and below.
+    FloydSteinberg,
+    /// Atkinson error diffusion: three quarters of the error is passed on,
+    /// over a wider area, for higher contrast and less noise.
+    Atkinson,
+    /// Ordered dithering with an 8×8 Bayer matrix. Each pixel is handled on
+    /// its own, so the pattern is stable from frame to frame.
+    Bayer,
+}
+
+/// The 8×8 Bayer threshold matrix.
+const BAYER: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// Where Floyd–Steinberg sends a pixel's error, as `(dx, dy, share)`.
+const FLOYD_STEINBERG: [(isize, usize, f64); 4] = [
+    (1, 0, 7.0 / 16.0),
+    (-1, 1, 3.0 / 16.0),
+    (0, 1, 5.0 / 16.0),
+    (1, 1, 1.0 / 16.0),
+];
+
+/// Where Atkinson sends a pixel's error, as `(dx, dy, share)`.
+const ATKINSON: [(isize, usize, f64); 6] = [
+    (1, 0, 1.0 / 8.0),
+    (2, 0, 1.0 / 8.0),
+    (-1, 1, 1.0 / 8.0),
+    (0, 1, 1.0 / 8.0),
+    (1, 1, 1.0 / 8.0),
+    (0, 2, 1.0 / 8.0),
+];
+
+/// Checks that `pixels` holds exactly `channels` bytes for every pixel.
+pub(crate) fn check_buffer(
+    width: usize,
+    height: usize,
+    channels: usize,
+    pixels: &[u8],
+) -> Result<(), ColorConversionError> {
+    let Some(expected) = width
+        .checked_mul(height)
+        .and_then(|n| n.checked_mul(channels))
+    else {
+        return Err(ColorConversionError::InvalidRgb(format!(
+            "a {}x{} image is too large",
+            width, height
+        )));
+    };
+    if pixels.len() != expected {
+        return Err(ColorConversionError::InvalidRgb(format!(
+            "expected {} bytes for a {}x{} image, found {}",
+            expected,
+            width,
+            height,
+            pixels.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Maps the pixels of a raw RGB image to palette indices, dithering to hide
+/// banding.
+///
+/// Errors are measured and spread in linear light rather than on the
+/// gamma-encoded sRGB values, so dithered areas keep the brightness of the
+/// original. ([`Dither::Atkinson`] loses part of the error on purpose, so
+/// midtones come out a little lighter or darker.)
+///
+/// # Arguments
+///
+/// * `width` - The width of the image, in pixels.
+/// * `height` - The height of the image, in pixels.
+/// * `rgb` - The pixels, row by row from the top left, as `r, g, b` bytes.
+/// * `palette` - The colors to map to, e.g. [`Palette::xterm256`].
+/// * `method` - The dithering method.
+///
+/// # Returns
+///
+/// The palette index of each pixel, row by row. With [`Palette::xterm256`]
+/// or [`Palette::ansi16`] these are ANSI color numbers.
+///
+/// # Errors
+///
+/// Returns [`ColorConversionError::InvalidRgb`] if `rgb` is not
+/// `width * height * 3` bytes long.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::{dither, Dither, Palette};
+///
+/// // A flat mid gray, with only black and white to draw it with
+/// let mono = Palette::new(["#000000", "#FFFFFF"])?;
+/// let indices = dither(4, 4, &[188; 48], &mono, Dither::FloydSteinberg)?;
+///
+/// // In linear light, #BCBCBC is half as bright as white
+/// assert_eq!(indices.iter().filter(|&&i| i == 1).count(), 8);
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+pub fn dither(
+    width: usize,
+    height: usize,
+    rgb: &[u8],
+    palette: &Palette,
+    method: Dither,
+) -> Result<Vec<u8>, ColorConversionError> {
+    check_buffer(width, height, 3, rgb)?;
+    if width == 0 {
+        return Ok(Vec::new());
+    }
+    let rows: Vec<Vec<[f64; 3]>> = rgb
+        .chunks(width * 3)
+        .take(height)
+        .map(|row| {
+            row.chunks(3)
+                .map(|pixel| [0, 1, 2].map(|c| srgb_to_linear(pixel[c])))
+                .collect()
+        })
+        .collect();
+    Ok(quantize(rows, palette, method).concat())
+}
+
+/// Maps rows of RGB colors to palette indices, dithering to hide banding.
+///
+/// Works like [`dither`], for colors that are already grouped in rows, such
+/// as a grid of gradient colors. Rows may have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::{dither_grid, Dither, Palette};
+///
+/// let ramp: Vec<[u8; 3]> = (0..8).map(|i| [i * 32, 0, 0]).collect();
+/// let rows = dither_grid(&[ramp.clone(), ramp], &Palette::xterm256(), Dither::Bayer);
+/// assert_eq!(rows.len(), 2);
+/// assert_eq!(rows[0][0], 16);
+/// ```
+pub fn dither_grid<R: AsRef<[[u8; 3]]>>(
+    rows: &[R],
+    palette: &Palette,
+    method: Dither,
+) -> Vec<Vec<u8>> {
+    let rows = rows
+        .iter()
+        .map(|row| {
+            row.as_ref()
+                .iter()
+                .map(|rgb| rgb.map(srgb_to_linear))
+                .collect()
+        })
+        .collect();
+    quantize(rows, palette, method)
+}
+
+/// Maps rows of linear-light colors to palette indices.
+fn quantize(mut rows: Vec<Vec<[f64; 3]>>, palette: &Palette, method: Dither) -> Vec<Vec<u8>> {
+    let kernel: &[(isize, usize, f64)] = match method {
+        Dither::FloydSteinberg => &FLOYD_STEINBERG,
+        Dither::Atkinson => &ATKINSON,
+        Dither::None | Dither::Bayer => &[],
+    };
+    // How far the Bayer thresholds push a color: about one palette step
+    let spread = 1.0 / (palette.len() as f64).cbrt();
+    let linear: Vec<[f64; 3]> = palette
+        .entries()
+        .iter()
+        .map(|(_, rgb)| rgb.map(srgb_to_linear))
+        .collect();
+
+    let mut out = Vec::with_capacity(rows.len());
+    for y in 0..rows.len() {
+        let mut indices = Vec::with_capacity(rows[y].len());
+        for x in 0..rows[y].len() {
+            let mut color = rows[y][x].map(|c| c.clamp(0.0, 1.0));
+            if method == Dither::Bayer {
+                let threshold = (BAYER[y % 8][x % 8] as f64 + 0.5) / 64.0 - 0.5;
+                color = color.map(|c| (c + threshold * spread).clamp(0.0, 1.0));
+            }
+
+            let position = palette.nearest_linear(color);
+            indices.push(palette.entries()[position].0);
+
+            let error = [0, 1, 2].map(|c| color[c] - linear[position][c]);
+            for &(dx, dy, share) in kernel {
+                let Some(row) = rows.get_mut(y + dy) else {
+                    continue;
+                };
+                let Some(pixel) = x.checked_add_signed(dx).and_then(|x| row.get_mut(x)) else {
+                    continue;
+                };
+                for c in 0..3 {
+                    pixel[c] += error[c] * share;
+                }
+            }
+        }
+        out.push(indices);
+    }
+    out
+}
@@ -0,0 +1,108 @@
+use super::{
+    ansi::{rgb_from_color_definition, IntoRgb},
+    error::ColorConversionError,
+    rgb::rgb_to_ansi8,
+    value::Color,
+};
+
+/// How many colors the terminal can show, and so which kind of [`Color`] to
+/// generate for computed colors such as gradients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorDepth {
+    /// 24-bit TrueColor, like [`color_rgb`](crate::format::color_rgb).
+    #[default]
+    TrueColor,
+    /// The 256-color palette, like [`color_ansi`](crate::format::color_ansi).
+    Ansi256,
+}
+
+impl ColorDepth {
+    /// Returns the [`Color`] to use for an RGB value at this depth.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminal_style::color::{Color, ColorDepth};
+    ///
+    /// assert_eq!(ColorDepth::TrueColor.color([255, 0, 0]), Color::Rgb([255, 0, 0]));
+    /// assert_eq!(ColorDepth::Ansi256.color([255, 0, 0]), Color::Ansi256(196));
+    /// ```
+    pub fn color(self, rgb: [u8; 3]) -> Color {
+        match self {
+            ColorDepth::TrueColor => Color::Rgb(rgb),
+            ColorDepth::Ansi256 => Color::Ansi256(rgb_to_ansi8(rgb)),
+        }
+    }
+}
+
+/// A smooth transition through two or more evenly spaced colors.
+///
+/// A gradient with a single color is solid.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Gradient;
+///
+/// let heat = Gradient::new(["#00FF00", "#FFFF00", "#FF0000"])?;
+/// assert_eq!(heat.at(0.0), [0, 255, 0]);
+/// assert_eq!(heat.at(0.25), [128, 255, 0]);
+/// assert_eq!(heat.at(1.0), [255, 0, 0]);
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Gradient {
+    stops: Vec<[u8; 3]>,
+}
+
+impl Gradient {
+    /// Creates a gradient through `colors`, in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - Any types implementing [`IntoRgb`] (e.g., `#RRGGBB`, `[r, g, b]`, or `u8` ANSI).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColorConversionError`] if any color is invalid, or
+    /// [`ColorConversionError::InvalidRgb`] if there are no colors.
+    pub fn new<I, C>(colors: I) -> Result<Gradient, ColorConversionError>
+    where
+        I: IntoIterator<Item = C>,
+        C: IntoRgb,
+    {
+        let stops = colors
+            .into_iter()
+            .map(rgb_from_color_definition)
+            .collect::<Result<Vec<_>, _>>()?;
+        if stops.is_empty() {
+            return Err(ColorConversionError::InvalidRgb(
+                "a gradient needs at least one color".to_string(),
+            ));
+        }
+        Ok(Gradient { stops })
+    }
+
+    /// Returns the color at `t`, from `0.0` (the first color) to `1.0` (the
+    /// last). Values outside that range are clamped.
+    pub fn at(&self, t: f64) -> [u8; 3] {
+        let last = self.stops.len() - 1;
+        if last == 0 {
+            return self.stops[0];
+        }
+
+        let position = t.clamp(0.0, 1.0) * last as f64;
+        let index = (position.floor() as usize).min(last - 1);
+        let fraction = position - index as f64;
+        let (from, to) = (self.stops[index], self.stops[index + 1]);
+        [0, 1, 2]
+            .map(|i| (from[i] as f64 + (to[i] as f64 - from[i] as f64) * fraction).round() as u8)
+    }
+}
+
+/// A solid gradient of a single color.
+impl From<[u8; 3]> for Gradient {
+    fn from(rgb: [u8; 3]) -> Self {
+        Gradient { stops: vec![rgb] }
+    }
+}
@@ -1,9 +1,13 @@
-use super::rgb::rgb_to_ansi8;
+use super::{error::ColorConversionError, rgb::rgb_to_ansi8};
 
 /// Converts a hex color string (e.g. "#FF00AA" or "F0A") to an RGB array.
 ///
-/// This function assumes that the input is always valid and performs no error checking.
 /// It supports both 6-character ("RRGGBB") and 3-character ("RGB") hex codes, with or without a leading `#`.
+/// Use [`try_hex_to_rgb`] for input that has not been validated.
+///
+/// # Panics
+///
+/// Panics if the input is not a valid 3- or 6-digit hex string.
 ///
 /// # Examples
 ///
@@ -12,20 +16,81 @@ use super::rgb::rgb_to_ansi8;
 /// assert_eq!(terminal_style::color::hex_to_rgb("F0A"), [255, 0, 170]);
 /// ```
 pub fn hex_to_rgb(hex: &str) -> [u8; 3] {
-    let hex = hex.trim_start_matches('#');
-
-    if hex.len() == 6 {
-        // Full 6-digit hex code: extract and convert each component
-        let r = u8::from_str_radix(&hex[0..2], 16).unwrap();
-        let g = u8::from_str_radix(&hex[2..4], 16).unwrap();
-        let b = u8::from_str_radix(&hex[4..6], 16).unwrap();
-        [r, g, b]
-    } else {
-        // 3-digit shorthand: duplicate each character
-        let r = u8::from_str_radix(&hex[0..1].repeat(2), 16).unwrap();
-        let g = u8::from_str_radix(&hex[1..2].repeat(2), 16).unwrap();
-        let b = u8::from_str_radix(&hex[2..3].repeat(2), 16).unwrap();
-        [r, g, b]
+    match checked_hex_to_rgb(hex) {
+        Some(rgb) => rgb,
+        None => panic!("invalid hex color: {:?}", hex),
+    }
+}
+
+/// Converts a hex color string to an RGB array, validating it first.
+///
+/// Accepts the same formats as [`hex_to_rgb`], but never panics: any input
+/// rejected by [`checked_hex_to_rgb`] is returned as an error instead.
+///
+/// # Errors
+///
+/// Returns [`ColorConversionError::InvalidHex`] if the input is not a valid
+/// 3- or 6-digit hex string.
+///
+/// # Examples
+///
+/// ```
+/// use terminal_style::color::try_hex_to_rgb;
+///
+/// assert_eq!(try_hex_to_rgb("#FF00AA").unwrap(), [255, 0, 170]);
+/// assert!(try_hex_to_rgb("#FF00").is_err());
+/// assert!(try_hex_to_rgb("ééé").is_err());
+/// ```
+pub fn try_hex_to_rgb(hex: &str) -> Result<[u8; 3], ColorConversionError> {
+    checked_hex_to_rgb(hex).ok_or_else(|| invalid_hex(hex))
+}
+
+/// Converts a hex color string to an RGB array in a `const` context.
+///
+/// Accepts 3 or 6 hex digits with an optional leading `#`, and returns `None` for
+/// anything else. Being a `const fn`, it backs the compile-time checked
+/// [`hex!`](crate::hex) macro as well as [`try_hex_to_rgb`] and [`validate_hex`](super::validate_hex).
+///
+/// # Examples
+///
+/// ```
+/// use terminal_style::color::checked_hex_to_rgb;
+///
+/// const PINK: Option<[u8; 3]> = checked_hex_to_rgb("#FF1493");
+/// assert_eq!(PINK, Some([255, 20, 147]));
+/// assert_eq!(checked_hex_to_rgb("#FF14"), None);
+/// ```
+pub const fn checked_hex_to_rgb(hex: &str) -> Option<[u8; 3]> {
+    let mut digits = hex.as_bytes();
+    if let [b'#', rest @ ..] = digits {
+        digits = rest;
+    }
+
+    let mut values = [0u8; 6];
+    let mut i = 0;
+    while i < digits.len() {
+        if i >= values.len() {
+            return None;
+        }
+        values[i] = match digits[i] {
+            b @ b'0'..=b'9' => b - b'0',
+            b @ b'a'..=b'f' => b - b'a' + 10,
+            b @ b'A'..=b'F' => b - b'A' + 10,
+            _ => return None,
+        };
+        i += 1;
+    }
+
+    match digits.len() {
+        // Full 6-digit hex code: combine pairs of digits
+        6 => Some([
+            values[0] * 16 + values[1],
+            values[2] * 16 + values[3],
+            values[4] * 16 + values[5],
+        ]),
+        // 3-digit shorthand: duplicate each digit
+        3 => Some([values[0] * 17, values[1] * 17, values[2] * 17]),
+        _ => None,
     }
 }
 
@@ -34,6 +99,11 @@ pub fn hex_to_rgb(hex: &str) -> [u8; 3] {
 /// This function first converts the hex color to RGB using [`hex_to_rgb`],
 /// then maps the RGB value to the corresponding ANSI 8-bit color using [`rgb_to_ansi8`].
 ///
+/// # Panics
+///
+/// Panics if the input is not a valid 3- or 6-digit hex string.
+/// Use [`try_hex_to_ansi8`] for input that has not been validated.
+///
 /// # Examples
 ///
 /// ```
@@ -44,3 +114,28 @@ pub fn hex_to_ansi8(hex: &str) -> u8 {
     let rgb = hex_to_rgb(hex);
     rgb_to_ansi8(rgb)
 }
+
+/// Converts a hex color string to an ANSI 8-bit color value, validating it first.
+///
+/// # Errors
+///
+/// Returns [`ColorConversionError::InvalidHex`] if the input is not a valid
+/// 3- or 6-digit hex string.
+///
+/// # Examples
+///
+/// ```
+/// use terminal_style::color::try_hex_to_ansi8;
+///
+/// assert_eq!(try_hex_to_ansi8("#FF0000").unwrap(), 196);
+/// assert!(try_hex_to_ansi8("#F0").is_err());
+/// ```
+pub fn try_hex_to_ansi8(hex: &str) -> Result<u8, ColorConversionError> {
+    let rgb = try_hex_to_rgb(hex)?;
+    Ok(rgb_to_ansi8(rgb))
+}
+
+/// The error for a string that is not a 3- or 6-digit hex color.
+pub(crate) fn invalid_hex(input: &str) -> ColorConversionError {
+    ColorConversionError::InvalidHex(format!("Expected 3 or 6-digit hex string, got: {}", input))
+}
@@ -0,0 +1,84 @@
+/// Converts a color from HSL (hue, saturation, lightness) to RGB.
+///
+/// # Arguments
+///
+/// * `hue` - The hue in degrees. Values outside `0.0..360.0` wrap around.
+/// * `saturation` - The saturation, from `0.0` (gray) to `1.0` (fully saturated).
+/// * `lightness` - The lightness, from `0.0` (black) through `0.5` (pure color) to `1.0` (white).
+///
+/// Saturation and lightness are clamped to `0.0..=1.0`.
+///
+/// # Returns
+///
+/// An array `[u8; 3]` representing red, green, and blue components.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::hsl_to_rgb;
+///
+/// assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), [255, 0, 0]);
+/// assert_eq!(hsl_to_rgb(240.0, 1.0, 0.5), [0, 0, 255]);
+/// assert_eq!(hsl_to_rgb(480.0, 1.0, 0.5), [0, 255, 0]); // Wraps to 120°
+/// ```
+pub fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> [u8; 3] {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let m = lightness - chroma / 2.0;
+    [r, g, b].map(|v| ((v + m) * 255.0).round() as u8)
+}
+
+/// Converts an RGB color to HSL (hue, saturation, lightness).
+///
+/// # Arguments
+///
+/// * `arr` - An array `[u8; 3]` representing red, green, and blue components.
+///
+/// # Returns
+///
+/// A tuple of the hue in degrees (`0.0..360.0`), and the saturation and
+/// lightness (`0.0..=1.0`). Grays have a hue and saturation of `0.0`.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::rgb_to_hsl;
+///
+/// assert_eq!(rgb_to_hsl([255, 0, 0]), (0.0, 1.0, 0.5));
+/// assert_eq!(rgb_to_hsl([255, 255, 255]), (0.0, 0.0, 1.0));
+/// ```
+pub fn rgb_to_hsl(arr: [u8; 3]) -> (f64, f64, f64) {
+    let [r, g, b] = arr.map(|v| v as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    (hue, saturation, lightness)
+}
@@ -4,17 +4,33 @@
 //! - **Hex**: `#RRGGBB` or `#RGB` strings.
 //! - **RGB**: `[u8; 3]` arrays.
 //! - **ANSI**: 8-bit color codes (0-255).
+//! - **HSL**: hue, saturation and lightness, for generating colors.
+//!
+//! [`Gradient`] blends between colors, for progress bars and color scales.
+//! [`dither`](fn@dither) maps images and gradients to a limited [`Palette`] without banding.
+//!
+//! The [`Color`] type wraps any of these while remembering which kind of color it is.
 //!
 //! It also handles validation of these formats and defines the [`ColorConversionError`] type.
 
 pub mod ansi;
+pub mod dither;
 pub mod error;
+pub mod gradient;
 pub mod hex;
+pub mod hsl;
+pub mod palette;
 pub mod rgb;
 pub mod validate;
+pub mod value;
 
 pub use ansi::{ansi8_to_hex, ansi8_to_rgb, rgb_from_color_definition, IntoRgb};
+pub use dither::{dither, dither_grid, Dither};
 pub use error::ColorConversionError;
-pub use hex::{hex_to_ansi8, hex_to_rgb};
+pub use gradient::{ColorDepth, Gradient};
+pub use hex::{checked_hex_to_rgb, hex_to_ansi8, hex_to_rgb, try_hex_to_ansi8, try_hex_to_rgb};
+pub use hsl::{hsl_to_rgb, rgb_to_hsl};
+pub use palette::Palette;
 pub use rgb::{rgb_to_ansi8, rgb_to_hex};
 pub use validate::{validate_ansi, validate_hex};
+pub use value::{Color, IntoColor};
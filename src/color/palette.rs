@@ -0,0 +1,136 @@
+use std::sync::OnceLock;
+
+use super::{
+    ansi::{ansi8_to_rgb, rgb_from_color_definition, IntoRgb},
+    error::ColorConversionError,
+};
+
+/// A set of up to 256 colors to quantize to, each with the index that
+/// stands for it, such as its ANSI color number.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Palette;
+///
+/// let xterm = Palette::xterm256();
+/// assert_eq!(xterm.nearest([250, 5, 5]), 196);
+///
+/// let mono = Palette::new(["#000000", "#FFFFFF"])?;
+/// assert_eq!(mono.nearest([200, 200, 200]), 1);
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Palette {
+    entries: Vec<(u8, [u8; 3])>,
+}
+
+impl Palette {
+    /// Creates a palette of `colors`, indexed by their position.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - Any types implementing [`IntoRgb`] (e.g., `#RRGGBB`, `[r, g, b]`, or `u8` ANSI).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColorConversionError`] if any color is invalid, or
+    /// [`ColorConversionError::InvalidRgb`] if there are no colors or more than 256.
+    pub fn new<I, C>(colors: I) -> Result<Palette, ColorConversionError>
+    where
+        I: IntoIterator<Item = C>,
+        C: IntoRgb,
+    {
+        let colors = colors
+            .into_iter()
+            .map(rgb_from_color_definition)
+            .collect::<Result<Vec<_>, _>>()?;
+        if colors.is_empty() || colors.len() > 256 {
+            return Err(ColorConversionError::InvalidRgb(format!(
+                "a palette needs 1 to 256 colors, found {}",
+                colors.len()
+            )));
+        }
+        Ok(Palette {
+            entries: (0..=255).zip(colors).collect(),
+        })
+    }
+
+    /// The 240 colors of the xterm 256-color palette that don't depend on the
+    /// terminal's theme: the 6×6×6 color cube and the gray ramp, indices 16
+    /// to 255. These are the colors [`rgb_to_ansi8`](super::rgb_to_ansi8) picks from.
+    pub fn xterm256() -> Palette {
+        Palette {
+            entries: (16..=255)
+                .map(|index| (index, ansi8_to_rgb(index)))
+                .collect(),
+        }
+    }
+
+    /// The 16 basic ANSI colors, indices 0 to 15, as xterm displays them.
+    /// Terminal themes often change these.
+    pub fn ansi16() -> Palette {
+        Palette {
+            entries: (0..16).map(|index| (index, ansi8_to_rgb(index))).collect(),
+        }
+    }
+
+    /// Returns the indices and colors of the palette.
+    pub fn entries(&self) -> &[(u8, [u8; 3])] {
+        &self.entries
+    }
+
+    /// Returns the number of colors.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the palette has no colors. Palettes are never empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the color with the given index, if it is in the palette.
+    pub fn get(&self, index: u8) -> Option<[u8; 3]> {
+        self.entries
+            .iter()
+            .find(|&&(i, _)| i == index)
+            .map(|&(_, rgb)| rgb)
+    }
+
+    /// Returns the index of the palette color closest to `rgb`, measured in
+    /// linear light.
+    pub fn nearest(&self, rgb: [u8; 3]) -> u8 {
+        self.entries[self.nearest_linear(rgb.map(srgb_to_linear))].0
+    }
+
+    /// Returns the position in `entries` of the color closest to a linear-light color.
+    pub(crate) fn nearest_linear(&self, linear: [f64; 3]) -> usize {
+        let mut best = (0, f64::INFINITY);
+        for (position, &(_, rgb)) in self.entries.iter().enumerate() {
+            let distance: f64 = (0..3)
+                .map(|channel| (srgb_to_linear(rgb[channel]) - linear[channel]).powi(2))
+                .sum();
+            if distance < best.1 {
+                best = (position, distance);
+            }
+        }
+        best.0
+    }
+}
+
+/// Converts an sRGB channel to linear light, from `0.0` to `1.0`.
+pub(crate) fn srgb_to_linear(value: u8) -> f64 {
+    static TABLE: OnceLock<[f64; 256]> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        std::array::from_fn(|i| {
+            let value = i as f64 / 255.0;
+            if value <= 0.04045 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        })
+    });
+    table[value as usize]
+}
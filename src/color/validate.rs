@@ -1,4 +1,7 @@
-use super::error::ColorConversionError;
+use super::{
+    error::ColorConversionError,
+    hex::{checked_hex_to_rgb, invalid_hex},
+};
 
 /// Validates whether a given string is a valid 3- or 6-digit hexadecimal color.
 ///
@@ -22,18 +25,10 @@ use super::error::ColorConversionError;
 /// assert!(terminal_style::color::validate_hex("abc").is_ok());
 /// assert!(terminal_style::color::validate_hex("xyz").is_err());
 /// ```
-
 pub fn validate_hex(input: &str) -> Result<(), ColorConversionError> {
-    let hex = input.strip_prefix('#').unwrap_or(input);
-
-    if !(hex.len() == 6 || hex.len() == 3) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
-        return Err(ColorConversionError::InvalidHex(format!(
-            "Expected 3 or 6-digit hex string, got: {}",
-            input
-        )));
-    }
-
-    Ok(())
+    checked_hex_to_rgb(input)
+        .map(|_| ())
+        .ok_or_else(|| invalid_hex(input))
 }
 
 /// Validates that an ANSI color value is within the 0–255 range.
@@ -0,0 +1,474 @@
+use std::{fmt, str::FromStr};
+
+use super::{
+    ansi::{ansi8_to_rgb, IntoRgb},
+    error::ColorConversionError,
+    hex::checked_hex_to_rgb,
+    rgb::rgb_to_hex,
+};
+
+/// Names of the 16 standard ANSI colors, indexed by their color number.
+const ANSI16_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright-black",
+    "bright-red",
+    "bright-green",
+    "bright-yellow",
+    "bright-blue",
+    "bright-magenta",
+    "bright-cyan",
+    "bright-white",
+];
+
+/// A terminal color that remembers how it was specified.
+///
+/// Unlike a bare `[u8; 3]`, a `Color` keeps track of whether it refers to one
+/// of the 16 theme colors, an index in the 256-color palette, an exact
+/// TrueColor value, or the terminal's default color. Escape sequences are
+/// generated in the same form, so `Color::Ansi16(4)` is emitted as `\x1b[34m`
+/// and picks up the user's theme blue instead of an RGB approximation.
+///
+/// # Parsing
+///
+/// Colors parse from strings via [`FromStr`]:
+/// - `"#FF1493"`, `"FF1493"`, `"#F0A"`: TrueColor hex. The `#` is optional,
+///   so any word of 3 or 6 hex digits is a color: `"bad"` and `"add"` parse
+///   as RGB. Inside a [`Style`](crate::format::Style) spec the `#` is
+///   required, so that a misspelled attribute is an error instead.
+/// - `"ansi:196"`: a palette index. Indices 0–15 become [`Color::Ansi16`].
+/// - `"ansi256:4"`: a palette index that is always a [`Color::Ansi256`], even
+///   below 16, so it is emitted as `38;5;4` rather than the theme color.
+/// - `"red"`, `"bright-blue"`, `"grey"`: one of the 16 named ANSI colors.
+/// - `"default"`: the terminal's default color.
+///
+/// [`Display`](fmt::Display) produces a string that parses back to the same
+/// value. The one exception is a `Color::Ansi16` above 15, which isn't one of
+/// the 16 colors: it is emitted and displayed as that palette index, and
+/// parses back as the equivalent [`Color::Ansi256`].
+///
+/// # Examples
+///
+/// ```
+/// use terminal_style::color::Color;
+///
+/// let blue: Color = "ansi:4".parse().unwrap();
+/// assert_eq!(blue, Color::Ansi16(4));
+/// assert_eq!(blue.to_string(), "blue");
+/// assert_eq!(blue.fg_sequence(), "\x1b[34m");
+///
+/// let pink: Color = "#FF1493".parse().unwrap();
+/// assert_eq!(pink.fg_sequence(), "\x1b[38;2;255;20;147m");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The terminal's default foreground or background color.
+    Default,
+    /// One of the 16 standard ANSI colors (0–7 normal, 8–15 bright).
+    Ansi16(u8),
+    /// An index in the 256-color palette.
+    Ansi256(u8),
+    /// A 24-bit TrueColor value.
+    Rgb([u8; 3]),
+}
+
+impl Color {
+    /// Returns the SGR parameters that select this color as the foreground.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminal_style::color::Color;
+    ///
+    /// assert_eq!(Color::Ansi16(9).fg_params(), "91");
+    /// assert_eq!(Color::Ansi256(196).fg_params(), "38;5;196");
+    /// assert_eq!(Color::Default.fg_params(), "39");
+    /// ```
+    pub fn fg_params(&self) -> String {
+        match *self {
+            Color::Default => "39".to_string(),
+            Color::Ansi16(n @ 0..=7) => format!("{}", 30 + n),
+            Color::Ansi16(n @ 8..=15) => format!("{}", 90 + n - 8),
+            Color::Ansi16(n) | Color::Ansi256(n) => format!("38;5;{}", n),
+            Color::Rgb([r, g, b]) => format!("38;2;{};{};{}", r, g, b),
+        }
+    }
+
+    /// Returns the SGR parameters that select this color as the background.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminal_style::color::Color;
+    ///
+    /// assert_eq!(Color::Ansi16(4).bg_params(), "44");
+    /// assert_eq!(Color::Rgb([0, 0, 255]).bg_params(), "48;2;0;0;255");
+    /// ```
+    pub fn bg_params(&self) -> String {
+        match *self {
+            Color::Default => "49".to_string(),
+            Color::Ansi16(n @ 0..=7) => format!("{}", 40 + n),
+            Color::Ansi16(n @ 8..=15) => format!("{}", 100 + n - 8),
+            Color::Ansi16(n) | Color::Ansi256(n) => format!("48;5;{}", n),
+            Color::Rgb([r, g, b]) => format!("48;2;{};{};{}", r, g, b),
+        }
+    }
+
+    /// Returns the complete escape sequence that sets this color as the foreground.
+    pub fn fg_sequence(&self) -> String {
+        format!("\x1b[{}m", self.fg_params())
+    }
+
+    /// Returns the complete escape sequence that sets this color as the background.
+    pub fn bg_sequence(&self) -> String {
+        format!("\x1b[{}m", self.bg_params())
+    }
+
+    /// Returns the RGB value of the color, or `None` for [`Color::Default`].
+    ///
+    /// Palette colors are converted with [`ansi8_to_rgb`].
+    pub fn to_rgb(&self) -> Option<[u8; 3]> {
+        match *self {
+            Color::Default => None,
+            Color::Ansi16(n) | Color::Ansi256(n) => Some(ansi8_to_rgb(n)),
+            Color::Rgb(rgb) => Some(rgb),
+        }
+    }
+
+    /// Returns the palette color for an ANSI index, using [`Color::Ansi16`]
+    /// for indices 0–15 and [`Color::Ansi256`] for the rest.
+    pub const fn from_ansi(index: u8) -> Color {
+        if index < 16 {
+            Color::Ansi16(index)
+        } else {
+            Color::Ansi256(index)
+        }
+    }
+
+    /// Parses a color spec in a `const` context.
+    ///
+    /// This is the parser behind [`Color::from_str`] and the [`style!`](crate::style)
+    /// macro. Named colors ignore case, `-`, `_` and spaces, and `gray`/`grey` are
+    /// accepted as aliases for `bright-black`.
+    pub(crate) const fn parse_spec(spec: &[u8]) -> Result<Color, SpecError> {
+        Color::parse_spec_with(spec, true)
+    }
+
+    /// Parses a color within a style spec, where hex colors need their
+    /// leading `#` so that words like `bad` aren't taken for colors.
+    pub(crate) const fn parse_style_spec(spec: &[u8]) -> Result<Color, SpecError> {
+        Color::parse_spec_with(spec, false)
+    }
+
+    /// Parses a color spec, reading words made only of hex digits as colors
+    /// if `bare_hex` is set.
+    const fn parse_spec_with(spec: &[u8], bare_hex: bool) -> Result<Color, SpecError> {
+        let spec = spec.trim_ascii();
+
+        if name_eq(spec, b"default") {
+            return Ok(Color::Default);
+        }
+        if name_eq(spec, b"gray") || name_eq(spec, b"grey") {
+            return Ok(Color::Ansi16(8));
+        }
+
+        let mut index = 0;
+        while index < ANSI16_NAMES.len() {
+            if name_eq(spec, ANSI16_NAMES[index].as_bytes()) {
+                return Ok(Color::Ansi16(index as u8));
+            }
+            index += 1;
+        }
+
+        if let [b'a', b'n', b's', b'i', b'2', b'5', b'6', b':', digits @ ..] = spec {
+            return match parse_i32(digits.trim_ascii()) {
+                Some(value @ 0..=255) => Ok(Color::Ansi256(value as u8)),
+                Some(value) => Err(SpecError::InvalidAnsi(value)),
+                None => Err(SpecError::UnknownFormat),
+            };
+        }
+        if let [b'a', b'n', b's', b'i', b':', digits @ ..] = spec {
+            return match parse_i32(digits.trim_ascii()) {
+                Some(value @ 0..=255) => Ok(Color::from_ansi(value as u8)),
+                Some(value) => Err(SpecError::InvalidAnsi(value)),
+                None => Err(SpecError::UnknownFormat),
+            };
+        }
+
+        let mut all_hex = bare_hex;
+        let mut i = 0;
+        while i < spec.len() {
+            all_hex &= spec[i].is_ascii_hexdigit();
+            i += 1;
+        }
+
+        if all_hex || matches!(spec, [b'#', ..]) {
+            // Hex digits are ASCII, so any valid hex spec is also valid UTF-8.
+            return match std::str::from_utf8(spec) {
+                Ok(hex) => match checked_hex_to_rgb(hex) {
+                    Some(rgb) => Ok(Color::Rgb(rgb)),
+                    None => Err(SpecError::InvalidHex),
+                },
+                Err(_) => Err(SpecError::InvalidHex),
+            };
+        }
+
+        Err(SpecError::UnknownFormat)
+    }
+}
+
+/// The reason a color or style spec was rejected by a `const` parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SpecError {
+    InvalidHex,
+    InvalidAnsi(i32),
+    UnknownFormat,
+    MissingBackground,
+    DuplicateForeground,
+    DuplicateBackground,
+}
+
+impl SpecError {
+    /// A fixed description of the error, used for compile-time panics.
+    pub(crate) const fn message(&self) -> &'static str {
+        match self {
+            SpecError::InvalidHex => "invalid hex color: expected 3 or 6 hex digits",
+            SpecError::InvalidAnsi(_) => "invalid ANSI color: expected an index from 0 to 255",
+            SpecError::UnknownFormat => "unknown color format",
+            SpecError::MissingBackground => "expected a background color after 'on'",
+            SpecError::DuplicateForeground => "style spec has more than one foreground color",
+            SpecError::DuplicateBackground => "style spec has more than one background color",
+        }
+    }
+
+    /// Converts the error into a [`ColorConversionError`] describing `input`.
+    pub(crate) fn into_error(self, input: &str) -> ColorConversionError {
+        match self {
+            SpecError::InvalidHex => ColorConversionError::InvalidHex(format!(
+                "Expected 3 or 6-digit hex string, got: {}",
+                input
+            )),
+            SpecError::InvalidAnsi(value) => ColorConversionError::InvalidAnsiValue(value),
+            SpecError::UnknownFormat => ColorConversionError::UnknownFormat(input.to_string()),
+            SpecError::MissingBackground => ColorConversionError::UnknownFormat(format!(
+                "Expected a background color after 'on' in: {}",
+                input
+            )),
+            SpecError::DuplicateForeground => ColorConversionError::UnknownFormat(format!(
+                "More than one foreground color in: {}",
+                input
+            )),
+            SpecError::DuplicateBackground => ColorConversionError::UnknownFormat(format!(
+                "More than one background color in: {}",
+                input
+            )),
+        }
+    }
+}
+
+/// Compares a spec against a lowercase color name, ignoring ASCII case and
+/// any `-`, `_` or space separators on either side.
+pub(crate) const fn name_eq(spec: &[u8], name: &[u8]) -> bool {
+    let (mut i, mut j) = (0, 0);
+    loop {
+        while i < spec.len() && matches!(spec[i], b'-' | b'_' | b' ') {
+            i += 1;
+        }
+        while j < name.len() && name[j] == b'-' {
+            j += 1;
+        }
+        if i == spec.len() || j == name.len() {
+            return i == spec.len() && j == name.len();
+        }
+        if spec[i].to_ascii_lowercase() != name[j] {
+            return false;
+        }
+        i += 1;
+        j += 1;
+    }
+}
+
+/// Parses an optionally signed decimal `i32`, returning `None` on any
+/// invalid digit or overflow.
+const fn parse_i32(digits: &[u8]) -> Option<i32> {
+    let (negative, digits) = match digits {
+        [b'-', rest @ ..] => (true, rest),
+        [b'+', rest @ ..] => (false, rest),
+        _ => (false, digits),
+    };
+    if digits.is_empty() {
+        return None;
+    }
+
+    let mut value: i64 = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        if !digits[i].is_ascii_digit() {
+            return None;
+        }
+        value = value * 10 + (digits[i] - b'0') as i64;
+        if value > i32::MAX as i64 + 1 {
+            return None;
+        }
+        i += 1;
+    }
+
+    let value = if negative { -value } else { value };
+    if value > i32::MAX as i64 {
+        return None;
+    }
+    Some(value as i32)
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Color::Default => write!(f, "default"),
+            Color::Ansi16(n) if n < 16 => write!(f, "{}", ANSI16_NAMES[n as usize]),
+            Color::Ansi256(n) if n < 16 => write!(f, "ansi256:{}", n),
+            Color::Ansi16(n) | Color::Ansi256(n) => write!(f, "ansi:{}", n),
+            Color::Rgb(rgb) => write!(f, "{}", rgb_to_hex(rgb)),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Color::parse_spec(s.as_bytes()).map_err(|err| err.into_error(s.trim()))
+    }
+}
+
+impl From<[u8; 3]> for Color {
+    fn from(rgb: [u8; 3]) -> Self {
+        Color::Rgb(rgb)
+    }
+}
+
+/// Implements conversion from a [`Color`] to an RGB array.
+///
+/// [`Color::Default`] has no RGB value and is reported as
+/// [`ColorConversionError::UnknownFormat`].
+impl IntoRgb for Color {
+    fn into_rgb(self) -> Result<[u8; 3], ColorConversionError> {
+        self.to_rgb()
+            .ok_or_else(|| ColorConversionError::UnknownFormat(self.to_string()))
+    }
+}
+
+/// A trait for converting different color formats into a [`Color`].
+///
+/// Accepts the same inputs as [`IntoRgb`], but keeps the kind of color
+/// the input describes instead of flattening it to RGB.
+pub trait IntoColor {
+    /// Converts the type into a [`Color`].
+    fn into_color(self) -> Result<Color, ColorConversionError>;
+}
+
+impl IntoColor for Color {
+    fn into_color(self) -> Result<Color, ColorConversionError> {
+        Ok(self)
+    }
+}
+
+/// Implements conversion from an RGB array to a TrueColor [`Color`].
+impl IntoColor for [u8; 3] {
+    fn into_color(self) -> Result<Color, ColorConversionError> {
+        Ok(Color::Rgb(self))
+    }
+}
+
+/// Implements conversion from a color string, as accepted by [`Color::from_str`].
+impl IntoColor for &str {
+    fn into_color(self) -> Result<Color, ColorConversionError> {
+        self.parse()
+    }
+}
+
+/// Implements conversion from a color string, as accepted by [`Color::from_str`].
+impl IntoColor for String {
+    fn into_color(self) -> Result<Color, ColorConversionError> {
+        self.parse()
+    }
+}
+
+/// Implements conversion from an ANSI 8-bit value to a palette [`Color`].
+impl IntoColor for u8 {
+    fn into_color(self) -> Result<Color, ColorConversionError> {
+        Ok(Color::from_ansi(self))
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::Color;
+    use crate::color::{validate_ansi, ColorConversionError};
+    use serde::de::{self, SeqAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt;
+
+    /// Serializes a color as its [`Display`](fmt::Display) string, e.g. `"#FF1493"` or `"ansi:196"`.
+    impl Serialize for Color {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    /// Deserializes a color from a string (any form accepted by [`Color::from_str`](std::str::FromStr)),
+    /// an ANSI index (`196`), or an `[r, g, b]` sequence.
+    impl<'de> Deserialize<'de> for Color {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(ColorVisitor)
+        }
+    }
+
+    struct ColorVisitor;
+
+    impl<'de> Visitor<'de> for ColorVisitor {
+        type Value = Color;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a color string, an ANSI index or an [r, g, b] array")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Color, E> {
+            v.parse().map_err(E::custom)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Color, E> {
+            let value = i32::try_from(v).unwrap_or(i32::MAX);
+            validate_ansi(value).map_err(E::custom)?;
+            Ok(Color::from_ansi(value as u8))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Color, E> {
+            self.visit_i64(i64::try_from(v).unwrap_or(i64::MAX))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Color, A::Error> {
+            let mut rgb = [0u8; 3];
+            for (index, component) in rgb.iter_mut().enumerate() {
+                let value: i64 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(index, &self))?;
+                *component = u8::try_from(value).map_err(|_| {
+                    de::Error::custom(ColorConversionError::InvalidRgb(format!(
+                        "component {} is out of range 0-255",
+                        value
+                    )))
+                })?;
+            }
+            if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                return Err(de::Error::invalid_length(4, &self));
+            }
+            Ok(Color::Rgb(rgb))
+        }
+    }
+}
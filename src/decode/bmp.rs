@@ -0,0 +1,171 @@
+//! Reading of uncompressed Windows bitmaps.
+//!
+//! Only 24-bit and 32-bit pixels are supported, stored as they are
+//! (`BI_RGB`) or with channel masks (`BI_BITFIELDS`). Palette-based and
+//! run-length encoded bitmaps are reported as unsupported.
+
+use super::{buffer_len, DecodeError, DecodedImage};
+
+/// The size of the file header, before the bitmap's own header.
+const FILE_HEADER: usize = 14;
+
+/// The size of the smallest supported bitmap header, `BITMAPINFOHEADER`.
+const INFO_HEADER: usize = 40;
+
+/// Pixels stored as they are.
+const BI_RGB: u32 = 0;
+
+/// Pixels whose channels are picked out by masks.
+const BI_BITFIELDS: u32 = 3;
+
+/// Like `BI_BITFIELDS`, with an alpha mask.
+const BI_ALPHABITFIELDS: u32 = 6;
+
+/// Reads a 24-bit or 32-bit uncompressed bitmap.
+///
+/// 32-bit bitmaps have alpha if their header gives an alpha mask, or if
+/// any of their unused fourth bytes are non-zero, as many programs write
+/// alpha there.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::UnknownFormat`] if the data doesn't start with
+/// `BM`, [`DecodeError::Unsupported`] for other bit depths, compression and
+/// old OS/2 headers, [`DecodeError::Invalid`] for a malformed header, and
+/// [`DecodeError::Truncated`] if pixels are missing.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::decode::bmp;
+///
+/// // A 1x1 24-bit bitmap of orange, stored as blue, green, red
+/// let mut file = b"BM".to_vec();
+/// file.extend(58u32.to_le_bytes());
+/// file.extend([0; 4]);
+/// file.extend(54u32.to_le_bytes());
+/// file.extend(40u32.to_le_bytes());
+/// file.extend(1i32.to_le_bytes());
+/// file.extend(1i32.to_le_bytes());
+/// file.extend(1u16.to_le_bytes());
+/// file.extend(24u16.to_le_bytes());
+/// file.extend([0; 24]);
+/// file.extend([0x00, 0x80, 0xFF, 0x00]);
+///
+/// let image = bmp::decode(&file)?;
+/// assert_eq!(image.pixels, vec![0xFF, 0x80, 0x00]);
+/// # Ok::<(), terminal_style::decode::DecodeError>(())
+/// ```
+pub fn decode(bytes: &[u8]) -> Result<DecodedImage, DecodeError> {
+    if !bytes.starts_with(b"BM") {
+        return Err(DecodeError::UnknownFormat);
+    }
+    let u32_at = |offset: usize| -> Result<u32, DecodeError> {
+        bytes
+            .get(offset..offset + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or(DecodeError::Truncated)
+    };
+    let u16_at = |offset: usize| -> Result<u16, DecodeError> {
+        bytes
+            .get(offset..offset + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .ok_or(DecodeError::Truncated)
+    };
+
+    let data_offset = u32_at(10)? as usize;
+    let header_size = u32_at(FILE_HEADER)? as usize;
+    if header_size < INFO_HEADER {
+        return Err(DecodeError::Unsupported(format!(
+            "{}-byte bitmap header",
+            header_size
+        )));
+    }
+    let width = u32_at(18)? as i32;
+    let height = u32_at(22)? as i32;
+    let bits = u16_at(28)?;
+    let compression = u32_at(30)?;
+
+    if width < 0 {
+        return Err(DecodeError::Invalid(format!("negative width {}", width)));
+    }
+    if bits != 24 && bits != 32 {
+        return Err(DecodeError::Unsupported(format!("{}-bit bitmaps", bits)));
+    }
+    // The masks follow the 40-byte header, or are its next fields
+    let masks = match (compression, bits) {
+        (BI_RGB, _) => None,
+        (BI_BITFIELDS | BI_ALPHABITFIELDS, 32) => {
+            let has_alpha = compression == BI_ALPHABITFIELDS || header_size >= 56;
+            let offset = FILE_HEADER + INFO_HEADER;
+            let alpha = if has_alpha { u32_at(offset + 12)? } else { 0 };
+            Some([
+                u32_at(offset)?,
+                u32_at(offset + 4)?,
+                u32_at(offset + 8)?,
+                alpha,
+            ])
+        }
+        _ => {
+            return Err(DecodeError::Unsupported(format!(
+                "compression method {} for {}-bit bitmaps",
+                compression, bits
+            )))
+        }
+    };
+
+    // Rows are stored bottom-up unless the height is negative, and padded
+    // to a multiple of four bytes
+    let top_down = height < 0;
+    let (width, height) = (width as usize, height.unsigned_abs() as usize);
+    let bytes_per_pixel = bits as usize / 8;
+    let stride = buffer_len(width, bytes_per_pixel, 1)?
+        .checked_next_multiple_of(4)
+        .ok_or(DecodeError::Truncated)?;
+    let data = bytes.get(data_offset..).ok_or(DecodeError::Truncated)?;
+    if data.len() / stride.max(1) < height {
+        return Err(DecodeError::Truncated);
+    }
+
+    let channels = if bits == 32 { 4 } else { 3 };
+    let mut pixels = Vec::with_capacity(buffer_len(width, height, channels)?);
+    for y in (0..height).filter(|_| width > 0) {
+        let row = if top_down { y } else { height - 1 - y };
+        let row = &data[row * stride..row * stride + width * bytes_per_pixel];
+        for pixel in row.chunks(bytes_per_pixel) {
+            match masks {
+                Some(masks) => {
+                    let value = u32::from_le_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+                    pixels.extend(masks.map(|mask| extract(value, mask)));
+                }
+                None if bits == 32 => pixels.extend([pixel[2], pixel[1], pixel[0], pixel[3]]),
+                None => pixels.extend([pixel[2], pixel[1], pixel[0]]),
+            }
+        }
+    }
+
+    let alpha = match masks {
+        Some([_, _, _, alpha]) => alpha != 0,
+        None => bits == 32 && pixels.chunks(4).any(|p| p[3] != 0),
+    };
+    if bits == 32 && !alpha {
+        pixels = pixels.chunks(4).flat_map(|p| [p[0], p[1], p[2]]).collect();
+    }
+    Ok(DecodedImage {
+        width,
+        height,
+        alpha,
+        pixels,
+    })
+}
+
+/// Picks the channel selected by `mask` out of a pixel, scaled to 8 bits.
+/// An empty mask reads as fully opaque, for images without alpha.
+fn extract(value: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 255;
+    }
+    let max = mask >> mask.trailing_zeros();
+    let sample = (value & mask) >> mask.trailing_zeros();
+    ((sample as u64 * 255 + max as u64 / 2) / max as u64) as u8
+}
@@ -0,0 +1,28 @@
+use std::fmt;
+
+/// Errors that can occur while reading an image file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data does not start with the signature of a supported format.
+    UnknownFormat,
+    /// The data ends before the image does.
+    Truncated,
+    /// The header or pixel data is malformed.
+    Invalid(String),
+    /// The file is well-formed but uses a feature that is not supported,
+    /// such as a compressed bitmap.
+    Unsupported(String),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnknownFormat => write!(f, "Unknown image format"),
+            DecodeError::Truncated => write!(f, "Image data ends early"),
+            DecodeError::Invalid(s) => write!(f, "Invalid image: {}", s),
+            DecodeError::Unsupported(s) => write!(f, "Unsupported image: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
@@ -0,0 +1,56 @@
+//! Reading of farbfeld images.
+//!
+//! A farbfeld file is the magic `farbfeld`, the width and height as 32-bit
+//! big-endian numbers, and then 16-bit big-endian RGBA samples, row by row.
+
+use super::{buffer_len, scale, DecodeError, DecodedImage};
+
+/// The eight bytes every farbfeld file starts with.
+pub(crate) const MAGIC: &[u8] = b"farbfeld";
+
+/// Reads a farbfeld image. Samples are reduced to 8 bits and the image
+/// always has alpha.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::UnknownFormat`] without the farbfeld magic, and
+/// [`DecodeError::Truncated`] if there are fewer pixels than the header says.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::decode::farbfeld;
+///
+/// let mut file = b"farbfeld".to_vec();
+/// file.extend([0, 0, 0, 1, 0, 0, 0, 1]);
+/// file.extend([0xFF, 0xFF, 0x80, 0x00, 0, 0, 0xFF, 0xFF]);
+/// let image = farbfeld::decode(&file)?;
+/// assert_eq!(image.pixels, vec![255, 128, 0, 255]);
+/// # Ok::<(), terminal_style::decode::DecodeError>(())
+/// ```
+pub fn decode(bytes: &[u8]) -> Result<DecodedImage, DecodeError> {
+    let Some(rest) = bytes.strip_prefix(MAGIC) else {
+        return Err(DecodeError::UnknownFormat);
+    };
+    if rest.len() < 8 {
+        return Err(DecodeError::Truncated);
+    }
+    let width = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+    let height = u32::from_be_bytes([rest[4], rest[5], rest[6], rest[7]]) as usize;
+
+    let samples = buffer_len(width, height, 4)?;
+    let data = &rest[8..];
+    if data.len() / 2 < samples {
+        return Err(DecodeError::Truncated);
+    }
+    let pixels = data[..samples * 2]
+        .chunks(2)
+        .map(|sample| scale(u16::from_be_bytes([sample[0], sample[1]]) as u32, 0xFFFF))
+        .collect();
+    Ok(DecodedImage {
+        width,
+        height,
+        alpha: true,
+        pixels,
+    })
+}
@@ -0,0 +1,135 @@
+//! Minimal readers for simple image files, for rendering them with
+//! [`format::image`](crate::format::image), [`format::sixel`](crate::format::sixel)
+//! and the other image encoders.
+//!
+//! Enabled by the `image-formats` feature. Supported formats:
+//! - **Netpbm**: binary and ASCII PBM, PGM and PPM (`P1` to `P6`), and PAM
+//!   (`P7`) with or without alpha, at up to 16 bits per sample.
+//! - **BMP**: uncompressed 24-bit and 32-bit bitmaps.
+//! - **farbfeld**: 16-bit RGBA.
+//!
+//! Every reader produces a [`DecodedImage`] with 8-bit samples. Malformed
+//! files are reported as a [`DecodeError`].
+
+pub mod bmp;
+pub mod error;
+pub mod farbfeld;
+pub mod pnm;
+
+pub use error::DecodeError;
+
+use crate::format::image::ImageData;
+
+/// An image read from a file: its size and 8-bit RGB or RGBA pixels.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecodedImage {
+    /// The width of the image, in pixels.
+    pub width: usize,
+    /// The height of the image, in pixels.
+    pub height: usize,
+    /// Whether `pixels` holds RGBA rather than RGB samples.
+    pub alpha: bool,
+    /// The pixels, row by row from the top left, as `r, g, b` or `r, g, b, a` bytes.
+    pub pixels: Vec<u8>,
+}
+
+impl DecodedImage {
+    /// The number of bytes per pixel: 4 with alpha, otherwise 3.
+    pub fn channels(&self) -> usize {
+        if self.alpha {
+            4
+        } else {
+            3
+        }
+    }
+
+    /// Borrows the image for the [`kitty`](crate::format::kitty) and
+    /// [`iterm2`](crate::format::iterm2) encoders.
+    pub fn as_image_data(&self) -> ImageData<'_> {
+        let (width, height, pixels) = (self.width, self.height, &self.pixels[..]);
+        if self.alpha {
+            ImageData::Rgba {
+                width,
+                height,
+                pixels,
+            }
+        } else {
+            ImageData::Rgb {
+                width,
+                height,
+                pixels,
+            }
+        }
+    }
+
+    /// Returns the pixels as RGB, blending any transparency over `background`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminal_style::decode::DecodedImage;
+    ///
+    /// let image = DecodedImage { width: 1, height: 1, alpha: true, pixels: vec![255, 0, 0, 128] };
+    /// assert_eq!(image.to_rgb([0, 0, 255]), vec![128, 0, 127]);
+    /// ```
+    pub fn to_rgb(&self, background: [u8; 3]) -> Vec<u8> {
+        if !self.alpha {
+            return self.pixels.clone();
+        }
+        self.pixels
+            .chunks(4)
+            .flat_map(|p| {
+                let alpha = p[3] as u32;
+                [0, 1, 2].map(|c| {
+                    ((p[c] as u32 * alpha + background[c] as u32 * (255 - alpha) + 127) / 255) as u8
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reads an image file, recognizing its format from its first bytes.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::UnknownFormat`] if the data is not a Netpbm, BMP
+/// or farbfeld file, and the errors of [`pnm::decode`], [`bmp::decode`] and
+/// [`farbfeld::decode`] otherwise.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::decode::{decode, DecodeError};
+///
+/// let image = decode(b"P3\n2 1\n255\n255 0 0  0 0 255\n")?;
+/// assert_eq!((image.width, image.height), (2, 1));
+/// assert_eq!(image.pixels, vec![255, 0, 0, 0, 0, 255]);
+///
+/// assert_eq!(decode(b"GIF89a"), Err(DecodeError::UnknownFormat));
+/// # Ok::<(), DecodeError>(())
+/// ```
+pub fn decode(bytes: &[u8]) -> Result<DecodedImage, DecodeError> {
+    match bytes {
+        [b'P', b'1'..=b'7', ..] => pnm::decode(bytes),
+        [b'B', b'M', ..] => bmp::decode(bytes),
+        _ if bytes.starts_with(farbfeld::MAGIC) => farbfeld::decode(bytes),
+        _ => Err(DecodeError::UnknownFormat),
+    }
+}
+
+/// Returns `width * height * channels`, if the image could fit in memory.
+pub(crate) fn buffer_len(
+    width: usize,
+    height: usize,
+    channels: usize,
+) -> Result<usize, DecodeError> {
+    width
+        .checked_mul(height)
+        .and_then(|n| n.checked_mul(channels))
+        .ok_or_else(|| DecodeError::Invalid(format!("{}x{} is too large", width, height)))
+}
+
+/// Scales a sample in `0..=max` to `0..=255`.
+pub(crate) fn scale(sample: u32, max: u32) -> u8 {
+    ((sample * 255 + max / 2) / max) as u8
+}
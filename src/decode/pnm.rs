@@ -0,0 +1,295 @@
+//! Reading of Netpbm images: PBM, PGM, PPM and PAM.
+//!
+//! Files start with `P` and a digit. `P1`, `P2` and `P3` are bitmaps,
+//! graymaps and pixmaps written as ASCII numbers; `P4`, `P5` and `P6` are
+//! the same in binary. `P7` is PAM, whose header names its depth and which
+//! may carry alpha.
+
+use super::{buffer_len, scale, DecodeError, DecodedImage};
+
+/// Reads a Netpbm image. Grayscale images are expanded to RGB, and samples
+/// of up to 16 bits are reduced to 8.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::UnknownFormat`] if the data doesn't start with
+/// `P1` to `P7`, [`DecodeError::Invalid`] for a malformed header or a sample
+/// above the maximum value, [`DecodeError::Unsupported`] for a PAM depth
+/// other than 1 to 4, and [`DecodeError::Truncated`] if pixels are missing.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::decode::pnm;
+///
+/// // A binary graymap, two pixels wide, with a comment
+/// let image = pnm::decode(b"P5 # dark to light\n2 1 255\n\x10\xF0")?;
+/// assert_eq!(image.pixels, vec![16, 16, 16, 240, 240, 240]);
+/// assert!(!image.alpha);
+/// # Ok::<(), terminal_style::decode::DecodeError>(())
+/// ```
+pub fn decode(bytes: &[u8]) -> Result<DecodedImage, DecodeError> {
+    let kind = match bytes {
+        [b'P', kind @ b'1'..=b'7', ..] => kind - b'0',
+        _ => return Err(DecodeError::UnknownFormat),
+    };
+    let mut header = Header { bytes, pos: 2 };
+    if kind == 7 {
+        return decode_pam(header);
+    }
+
+    let width = header.number("width")? as usize;
+    let height = header.number("height")? as usize;
+    let max = match kind {
+        1 | 4 => 1,
+        _ => header.number("maximum value")?,
+    };
+    if !(1..=0xFFFF).contains(&max) {
+        return Err(DecodeError::Invalid(format!(
+            "maximum value {} is not between 1 and 65535",
+            max
+        )));
+    }
+    let depth = if matches!(kind, 3 | 6) { 3 } else { 1 };
+    let count = buffer_len(width, height, depth)?;
+
+    let samples = match kind {
+        1 => header.bits(count)?,
+        2 | 3 => header.ascii_samples(count, max)?,
+        4 => header.packed_bits(width, height)?,
+        _ => header.binary_samples(count, max)?,
+    };
+    // Bitmaps use 1 for black
+    let samples: Vec<u8> = if matches!(kind, 1 | 4) {
+        samples
+            .iter()
+            .map(|&bit| if bit == 0 { 255 } else { 0 })
+            .collect()
+    } else {
+        samples
+    };
+    Ok(to_image(width, height, depth, samples))
+}
+
+/// Reads the rest of a PAM file, after its `P7`.
+fn decode_pam(mut header: Header) -> Result<DecodedImage, DecodeError> {
+    let (mut width, mut height, mut depth, mut max) = (None, None, None, None);
+    loop {
+        let key = header.token()?;
+        match key {
+            b"ENDHDR" => break,
+            b"WIDTH" => width = Some(header.number("width")?),
+            b"HEIGHT" => height = Some(header.number("height")?),
+            b"DEPTH" => depth = Some(header.number("depth")?),
+            b"MAXVAL" => max = Some(header.number("maximum value")?),
+            // The tuple type only names what the depth already says
+            b"TUPLTYPE" => header.skip_line(),
+            _ => {
+                return Err(DecodeError::Invalid(format!(
+                    "unknown PAM header field {}",
+                    String::from_utf8_lossy(key)
+                )))
+            }
+        }
+    }
+    // The pixels start on the line after ENDHDR
+    header.skip_line();
+
+    let missing = |field: &str| DecodeError::Invalid(format!("PAM header has no {}", field));
+    let width = width.ok_or_else(|| missing("WIDTH"))? as usize;
+    let height = height.ok_or_else(|| missing("HEIGHT"))? as usize;
+    let depth = depth.ok_or_else(|| missing("DEPTH"))? as usize;
+    let max = max.ok_or_else(|| missing("MAXVAL"))?;
+    if !(1..=4).contains(&depth) {
+        return Err(DecodeError::Unsupported(format!("PAM depth {}", depth)));
+    }
+    if !(1..=0xFFFF).contains(&max) {
+        return Err(DecodeError::Invalid(format!(
+            "maximum value {} is not between 1 and 65535",
+            max
+        )));
+    }
+
+    let count = buffer_len(width, height, depth)?;
+    let samples = header.samples_from(header.pos, count, max)?;
+    Ok(to_image(width, height, depth, samples))
+}
+
+/// Expands 8-bit samples with one to four channels to RGB or RGBA.
+fn to_image(width: usize, height: usize, depth: usize, samples: Vec<u8>) -> DecodedImage {
+    let pixels = match depth {
+        1 => samples.iter().flat_map(|&v| [v, v, v]).collect(),
+        2 => samples
+            .chunks(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        _ => samples,
+    };
+    DecodedImage {
+        width,
+        height,
+        alpha: matches!(depth, 2 | 4),
+        pixels,
+    }
+}
+
+/// A position in a Netpbm header.
+struct Header<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Header<'a> {
+    /// Skips whitespace and `#` comments.
+    fn skip_space(&mut self) {
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            if byte == b'#' {
+                self.skip_line();
+            } else if byte.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Moves past the end of the current line.
+    fn skip_line(&mut self) {
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            self.pos += 1;
+            if byte == b'\n' {
+                break;
+            }
+        }
+    }
+
+    /// Reads the next run of characters up to whitespace or a comment.
+    fn token(&mut self) -> Result<&'a [u8], DecodeError> {
+        self.skip_space();
+        let start = self.pos;
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            if byte.is_ascii_whitespace() || byte == b'#' {
+                break;
+            }
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(DecodeError::Truncated);
+        }
+        Ok(&self.bytes[start..self.pos])
+    }
+
+    /// Reads a decimal number, naming it in the error if it isn't one.
+    fn number(&mut self, name: &str) -> Result<u32, DecodeError> {
+        let token = self.token()?;
+        std::str::from_utf8(token)
+            .ok()
+            .filter(|s| s.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| {
+                DecodeError::Invalid(format!(
+                    "expected the {}, found {}",
+                    name,
+                    String::from_utf8_lossy(token)
+                ))
+            })
+    }
+
+    /// Reads `count` ASCII samples of at most `max`, scaled to 8 bits.
+    fn ascii_samples(&mut self, count: usize, max: u32) -> Result<Vec<u8>, DecodeError> {
+        // Every sample takes at least a byte, so this bounds the allocation
+        if count > self.bytes.len() - self.pos {
+            return Err(DecodeError::Truncated);
+        }
+        (0..count)
+            .map(|_| {
+                let sample = self.number("sample")?;
+                check_sample(sample, max)?;
+                Ok(scale(sample, max))
+            })
+            .collect()
+    }
+
+    /// Reads `count` ASCII bits, which need not be separated.
+    fn bits(&mut self, count: usize) -> Result<Vec<u8>, DecodeError> {
+        if count > self.bytes.len() - self.pos {
+            return Err(DecodeError::Truncated);
+        }
+        (0..count)
+            .map(|_| {
+                self.skip_space();
+                let byte = *self.bytes.get(self.pos).ok_or(DecodeError::Truncated)?;
+                self.pos += 1;
+                match byte {
+                    b'0' | b'1' => Ok(byte - b'0'),
+                    _ => Err(DecodeError::Invalid(format!(
+                        "expected 0 or 1, found {}",
+                        byte.escape_ascii()
+                    ))),
+                }
+            })
+            .collect()
+    }
+
+    /// Reads a binary bitmap, whose rows are padded to whole bytes.
+    fn packed_bits(&mut self, width: usize, height: usize) -> Result<Vec<u8>, DecodeError> {
+        let data = self.binary_data()?;
+        let row_bytes = width.div_ceil(8);
+        let length = buffer_len(row_bytes, height, 1)?;
+        if data.len() < length {
+            return Err(DecodeError::Truncated);
+        }
+        if width == 0 {
+            return Ok(Vec::new());
+        }
+        Ok(data[..length]
+            .chunks(row_bytes)
+            .flat_map(|row| (0..width).map(move |x| (row[x / 8] >> (7 - x % 8)) & 1))
+            .collect())
+    }
+
+    /// Reads `count` binary samples, one byte each if `max` is below 256
+    /// and two bytes each otherwise.
+    fn binary_samples(&mut self, count: usize, max: u32) -> Result<Vec<u8>, DecodeError> {
+        let start = self.pos + 1;
+        self.binary_data()?;
+        self.samples_from(start, count, max)
+    }
+
+    /// Skips the single whitespace character between the header and binary
+    /// data, and returns the data.
+    fn binary_data(&mut self) -> Result<&'a [u8], DecodeError> {
+        match self.bytes.get(self.pos) {
+            Some(byte) if byte.is_ascii_whitespace() => Ok(&self.bytes[self.pos + 1..]),
+            _ => Err(DecodeError::Truncated),
+        }
+    }
+
+    /// Reads `count` binary samples starting at `start`.
+    fn samples_from(&self, start: usize, count: usize, max: u32) -> Result<Vec<u8>, DecodeError> {
+        let data = self.bytes.get(start..).unwrap_or_default();
+        let size = if max > 0xFF { 2 } else { 1 };
+        if data.len() / size < count {
+            return Err(DecodeError::Truncated);
+        }
+        data[..count * size]
+            .chunks(size)
+            .map(|sample| {
+                let sample = sample.iter().fold(0, |v, &byte| v << 8 | byte as u32);
+                check_sample(sample, max)?;
+                Ok(scale(sample, max))
+            })
+            .collect()
+    }
+}
+
+/// Checks that a sample is no greater than the maximum value.
+fn check_sample(sample: u32, max: u32) -> Result<(), DecodeError> {
+    if sample > max {
+        return Err(DecodeError::Invalid(format!(
+            "sample {} is above the maximum value {}",
+            sample, max
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+/// The characters used to draw a border, including the junctions needed
+/// where inner lines meet the edges (as in tables).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BorderChars {
+    /// Top left corner.
+    pub top_left: char,
+    /// Top right corner.
+    pub top_right: char,
+    /// Bottom left corner.
+    pub bottom_left: char,
+    /// Bottom right corner.
+    pub bottom_right: char,
+    /// Top and bottom edges, and horizontal inner lines.
+    pub horizontal: char,
+    /// Left and right edges, and vertical inner lines.
+    pub vertical: char,
+    /// Where a vertical inner line meets the top edge.
+    pub top_junction: char,
+    /// Where a vertical inner line meets the bottom edge.
+    pub bottom_junction: char,
+    /// Where a horizontal inner line meets the left edge.
+    pub left_junction: char,
+    /// Where a horizontal inner line meets the right edge.
+    pub right_junction: char,
+    /// Where inner lines cross.
+    pub cross: char,
+}
+
+impl BorderChars {
+    /// `┌─┐` light box-drawing lines.
+    pub const SINGLE: BorderChars =
+        BorderChars::new(['┌', '┐', '└', '┘', '─', '│', '┬', '┴', '├', '┤', '┼']);
+    /// `╔═╗` double box-drawing lines.
+    pub const DOUBLE: BorderChars =
+        BorderChars::new(['╔', '╗', '╚', '╝', '═', '║', '╦', '╩', '╠', '╣', '╬']);
+    /// `╭─╮` light lines with rounded corners.
+    pub const ROUNDED: BorderChars =
+        BorderChars::new(['╭', '╮', '╰', '╯', '─', '│', '┬', '┴', '├', '┤', '┼']);
+    /// `┏━┓` heavy box-drawing lines.
+    pub const HEAVY: BorderChars =
+        BorderChars::new(['┏', '┓', '┗', '┛', '━', '┃', '┳', '┻', '┣', '┫', '╋']);
+    /// `+-+` plain ASCII, for terminals without box-drawing characters.
+    pub const ASCII: BorderChars =
+        BorderChars::new(['+', '+', '+', '+', '-', '|', '+', '+', '+', '+', '+']);
+
+    /// Creates a set of border characters, in the order the fields are declared:
+    /// the four corners, the horizontal and vertical lines, then the top,
+    /// bottom, left and right junctions and the cross.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminal_style::format::BorderChars;
+    ///
+    /// let stars = BorderChars::new(['*'; 11]);
+    /// assert_eq!(stars.top_left, '*');
+    /// ```
+    pub const fn new(chars: [char; 11]) -> BorderChars {
+        let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical, top_junction, bottom_junction, left_junction, right_junction, cross] =
+            chars;
+        BorderChars {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+            horizontal,
+            vertical,
+            top_junction,
+            bottom_junction,
+            left_junction,
+            right_junction,
+            cross,
+        }
+    }
+}
+
+/// A border style for boxes and tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Border {
+    /// No border at all.
+    None,
+    /// `┌─┐` light box-drawing lines.
+    #[default]
+    Single,
+    /// `╔═╗` double box-drawing lines.
+    Double,
+    /// `╭─╮` light lines with rounded corners.
+    Rounded,
+    /// `┏━┓` heavy box-drawing lines.
+    Heavy,
+    /// `+-+` plain ASCII.
+    Ascii,
+    /// Any other set of characters.
+    Custom(BorderChars),
+}
+
+impl Border {
+    /// Returns the characters to draw the border with, or `None` for [`Border::None`].
+    pub const fn chars(&self) -> Option<BorderChars> {
+        match self {
+            Border::None => None,
+            Border::Single => Some(BorderChars::SINGLE),
+            Border::Double => Some(BorderChars::DOUBLE),
+            Border::Rounded => Some(BorderChars::ROUNDED),
+            Border::Heavy => Some(BorderChars::HEAVY),
+            Border::Ascii => Some(BorderChars::ASCII),
+            Border::Custom(chars) => Some(*chars),
+        }
+    }
+}
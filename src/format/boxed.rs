@@ -0,0 +1,277 @@
+use super::{
+    border::Border,
+    layout::{pad, HAlign, ToLines},
+    sgr,
+    style::Style,
+    width::visible_width,
+};
+use crate::color::Color;
+
+/// Space around the four sides of a box, in lines (top and bottom) and
+/// columns (left and right).
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::Spacing;
+///
+/// assert_eq!(Spacing::symmetric(1, 2), Spacing::new(1, 2, 1, 2));
+/// assert_eq!(Spacing::from(1), Spacing::all(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Spacing {
+    /// Lines above the content.
+    pub top: usize,
+    /// Columns to the right of the content.
+    pub right: usize,
+    /// Lines below the content.
+    pub bottom: usize,
+    /// Columns to the left of the content.
+    pub left: usize,
+}
+
+impl Spacing {
+    /// Creates spacing from each side, in CSS order: top, right, bottom, left.
+    pub const fn new(top: usize, right: usize, bottom: usize, left: usize) -> Spacing {
+        Spacing {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// The same spacing on all four sides.
+    pub const fn all(n: usize) -> Spacing {
+        Spacing::new(n, n, n, n)
+    }
+
+    /// `vertical` lines above and below, `horizontal` columns left and right.
+    pub const fn symmetric(vertical: usize, horizontal: usize) -> Spacing {
+        Spacing::new(vertical, horizontal, vertical, horizontal)
+    }
+}
+
+impl From<usize> for Spacing {
+    fn from(n: usize) -> Self {
+        Spacing::all(n)
+    }
+}
+
+/// Describes how [`boxed`] draws a box: its border, colors, title, padding and margin.
+///
+/// The default is a single-line border with no colors, title, padding or margin.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Color;
+/// use terminal_style::format::{Border, BoxStyle, HAlign, Style};
+///
+/// let panel = BoxStyle::new()
+///     .border(Border::Rounded)
+///     .border_style(Style::new().fg(Color::Ansi16(4)))
+///     .title("Summary", HAlign::Center)
+///     .padding(1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BoxStyle {
+    border: Border,
+    border_style: Style,
+    background: Option<Color>,
+    title: Option<(String, HAlign)>,
+    padding: Spacing,
+    margin: Spacing,
+}
+
+impl BoxStyle {
+    /// Creates the default box style.
+    pub fn new() -> BoxStyle {
+        BoxStyle::default()
+    }
+
+    /// Sets the border characters.
+    pub fn border(mut self, border: Border) -> BoxStyle {
+        self.border = border;
+        self
+    }
+
+    /// Sets the style the border is drawn in, e.g. its color.
+    pub fn border_style(mut self, style: Style) -> BoxStyle {
+        self.border_style = style;
+        self
+    }
+
+    /// Fills the box, including its border and padding, with a background color.
+    ///
+    /// Content that sets its own background keeps it.
+    pub fn background(mut self, color: Color) -> BoxStyle {
+        self.background = Some(color);
+        self
+    }
+
+    /// Shows a title in the top edge. The title may already be styled.
+    ///
+    /// Titles need a border to be drawn in, so they are not shown with [`Border::None`].
+    pub fn title(mut self, title: impl Into<String>, align: HAlign) -> BoxStyle {
+        self.title = Some((title.into(), align));
+        self
+    }
+
+    /// Sets the space between the border and the content.
+    pub fn padding(mut self, padding: impl Into<Spacing>) -> BoxStyle {
+        self.padding = padding.into();
+        self
+    }
+
+    /// Sets the space around the outside of the border.
+    pub fn margin(mut self, margin: impl Into<Spacing>) -> BoxStyle {
+        self.margin = margin.into();
+        self
+    }
+
+    /// Draws a box around `content`. See [`boxed`].
+    pub fn render<T: ToLines + ?Sized>(&self, content: &T) -> Vec<String> {
+        let lines = content.to_lines();
+        let chars = self.border.chars();
+        let title = match (&self.title, chars) {
+            (Some((title, align)), Some(_)) => Some((title.as_str(), *align)),
+            _ => None,
+        };
+
+        let content_width = lines
+            .iter()
+            .map(|line| visible_width(line))
+            .max()
+            .unwrap_or(0);
+        // A title is surrounded by spaces, with at least one border character beyond each
+        let title_width = title.map_or(0, |(title, _)| visible_width(title));
+        let min_width = title.map_or(0, |_| title_width + 4);
+        let inner_width = (self.padding.left + content_width + self.padding.right).max(min_width);
+        let content_width = inner_width - self.padding.left - self.padding.right;
+
+        // Rows inside the margin: border edges, padding and content
+        let mut rows = Vec::with_capacity(lines.len() + 2);
+        let blank = " ".repeat(inner_width);
+        let padded = |line: &str| {
+            format!(
+                "{}{}{}",
+                " ".repeat(self.padding.left),
+                pad(line, content_width, HAlign::Left),
+                " ".repeat(self.padding.right)
+            )
+        };
+        rows.extend(std::iter::repeat_n(blank.clone(), self.padding.top));
+        rows.extend(lines.iter().map(|line| padded(line)));
+        rows.extend(std::iter::repeat_n(blank, self.padding.bottom));
+
+        if let Some(chars) = chars {
+            let edge = |text: String| self.border_style.paint(&text);
+            let side = edge(chars.vertical.to_string());
+            for row in &mut rows {
+                *row = format!("{}{}{}", side, row, side);
+            }
+
+            let horizontal = |n: usize| chars.horizontal.to_string().repeat(n);
+            let top = match title {
+                Some((title, align)) => {
+                    let (before, after) = align_title(align, inner_width - title_width - 2);
+                    format!(
+                        "{} {} {}",
+                        edge(format!("{}{}", chars.top_left, horizontal(before))),
+                        title,
+                        edge(format!("{}{}", horizontal(after), chars.top_right))
+                    )
+                }
+                None => edge(format!(
+                    "{}{}{}",
+                    chars.top_left,
+                    horizontal(inner_width),
+                    chars.top_right
+                )),
+            };
+            let bottom = edge(format!(
+                "{}{}{}",
+                chars.bottom_left,
+                horizontal(inner_width),
+                chars.bottom_right
+            ));
+            rows.insert(0, top);
+            rows.push(bottom);
+        }
+
+        if let Some(color) = self.background {
+            let base = Style::new().bg(color);
+            for row in &mut rows {
+                *row = sgr::restyle(row, |_, _, current| base.patch(&current));
+            }
+        }
+
+        let outer_width = rows.first().map_or(0, |row| visible_width(row));
+        let margin_width = self.margin.left + outer_width + self.margin.right;
+        let mut out = Vec::with_capacity(rows.len() + self.margin.top + self.margin.bottom);
+        out.extend(std::iter::repeat_n(
+            " ".repeat(margin_width),
+            self.margin.top,
+        ));
+        out.extend(rows.iter().map(|row| {
+            format!(
+                "{}{}{}",
+                " ".repeat(self.margin.left),
+                row,
+                " ".repeat(self.margin.right)
+            )
+        }));
+        out.extend(std::iter::repeat_n(
+            " ".repeat(margin_width),
+            self.margin.bottom,
+        ));
+        out
+    }
+}
+
+/// Splits the border next to a title into the lengths before and after it,
+/// keeping at least one border character on each side.
+fn align_title(align: HAlign, extra: usize) -> (usize, usize) {
+    match align {
+        HAlign::Left => (1, extra - 1),
+        HAlign::Center => (extra / 2, extra - extra / 2),
+        HAlign::Right => (extra - 1, 1),
+    }
+}
+
+/// Draws a box around a string or a block of lines.
+///
+/// The box is sized to fit the widest line, measured by visible width, so
+/// content that is already styled lines up. Strings are split at newlines.
+///
+/// # Arguments
+///
+/// * `content` - A string, or a slice or `Vec` of lines.
+/// * `style` - How to draw the box.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{bold, boxed, BoxStyle, HAlign};
+///
+/// let panel = boxed(&vec![bold("3 passed"), "0 failed".to_string()], &BoxStyle::new());
+/// assert_eq!(
+///     panel,
+///     vec![
+///         "┌────────┐",
+///         "│\x1b[1m3 passed\x1b[0m│",
+///         "│0 failed│",
+///         "└────────┘",
+///     ]
+/// );
+///
+/// let titled = boxed("ok", &BoxStyle::new().title("Tests", HAlign::Left).padding(1));
+/// assert_eq!(
+///     titled,
+///     vec!["┌─ Tests ─┐", "│         │", "│ ok      │", "│         │", "└─────────┘"]
+/// );
+/// ```
+pub fn boxed<T: ToLines + ?Sized>(content: &T, style: &BoxStyle) -> Vec<String> {
+    style.render(content)
+}
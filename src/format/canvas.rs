@@ -0,0 +1,321 @@
+//! A pixel canvas for drawing lines, rectangles and circles in the terminal.
+//!
+//! Pixels are packed into terminal cells as braille dots, half blocks or
+//! quadrant blocks, so a canvas has two to eight pixels per cell.
+
+use super::{
+    sgr::{self, Piece},
+    style::Style,
+};
+use crate::color::Color;
+
+/// Quadrant characters, indexed by a mask of the lit quarters: top left is
+/// 1, top right 2, bottom left 4 and bottom right 8.
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// How a [`Canvas`] packs pixels into terminal cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CanvasMode {
+    /// Braille dots: 2×4 pixels per cell, one color per cell.
+    #[default]
+    Braille,
+    /// Half blocks `▀▄`: 1×2 pixels per cell, each pixel with its own color.
+    HalfBlock,
+    /// Quadrant blocks `▖▘▗▝`: 2×2 pixels per cell, one color per cell.
+    Quadrant,
+}
+
+impl CanvasMode {
+    /// Returns the number of pixels across and down each cell.
+    pub const fn cell_size(&self) -> (usize, usize) {
+        match self {
+            CanvasMode::Braille => (2, 4),
+            CanvasMode::HalfBlock => (1, 2),
+            CanvasMode::Quadrant => (2, 2),
+        }
+    }
+}
+
+/// A grid of colored pixels drawn with braille or block characters, at a
+/// higher resolution than whole terminal cells.
+///
+/// Pixels are addressed from the top left corner. Coordinates are signed and
+/// anything drawn outside the canvas is clipped, so shapes may overlap its
+/// edges.
+///
+/// In [`Braille`](CanvasMode::Braille) and [`Quadrant`](CanvasMode::Quadrant)
+/// modes a cell can only show one color, so a cell whose pixels have
+/// different colors takes the most common one.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Color;
+/// use terminal_style::format::{Canvas, CanvasMode};
+///
+/// let mut canvas = Canvas::new(2, 1, CanvasMode::Braille);
+/// canvas.line(0, 0, 3, 3, Color::Ansi16(2));
+/// assert_eq!(canvas.render(), vec!["\x1b[32m⠑⢄\x1b[0m"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Canvas {
+    cols: usize,
+    rows: usize,
+    mode: CanvasMode,
+    pixels: Vec<Option<Color>>,
+}
+
+impl Canvas {
+    /// Creates an empty canvas `cols` cells wide and `rows` cells high.
+    pub fn new(cols: usize, rows: usize, mode: CanvasMode) -> Canvas {
+        let (cell_width, cell_height) = mode.cell_size();
+        Canvas {
+            cols,
+            rows,
+            mode,
+            pixels: vec![None; cols * cell_width * rows * cell_height],
+        }
+    }
+
+    /// Returns the width of the canvas in pixels.
+    pub fn width(&self) -> usize {
+        self.cols * self.mode.cell_size().0
+    }
+
+    /// Returns the height of the canvas in pixels.
+    pub fn height(&self) -> usize {
+        self.rows * self.mode.cell_size().1
+    }
+
+    fn index(&self, x: i64, y: i64) -> Option<usize> {
+        let (x, y) = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        (x < self.width() && y < self.height()).then(|| y * self.width() + x)
+    }
+
+    /// Sets a pixel, ignoring it if it is outside the canvas.
+    fn plot(&mut self, x: i64, y: i64, color: Color) {
+        if let Some(index) = self.index(x, y) {
+            self.pixels[index] = Some(color);
+        }
+    }
+
+    /// Returns the color of a pixel, or `None` if it is not set or outside the canvas.
+    pub fn get(&self, x: i32, y: i32) -> Option<Color> {
+        self.index(x.into(), y.into())
+            .and_then(|index| self.pixels[index])
+    }
+
+    /// Sets a pixel to `color`.
+    pub fn set(&mut self, x: i32, y: i32, color: Color) {
+        self.plot(x.into(), y.into(), color);
+    }
+
+    /// Clears a pixel, so it shows the terminal's background.
+    pub fn unset(&mut self, x: i32, y: i32) {
+        if let Some(index) = self.index(x.into(), y.into()) {
+            self.pixels[index] = None;
+        }
+    }
+
+    /// Clears every pixel.
+    pub fn clear(&mut self) {
+        self.pixels.fill(None);
+    }
+
+    /// Draws a line from `(x0, y0)` to `(x1, y1)`, including both ends, with
+    /// Bresenham's algorithm.
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: Color) {
+        self.draw_line([x0.into(), y0.into()], [x1.into(), y1.into()], color);
+    }
+
+    /// Draws a line, visiting only the pixels whose position along its longer
+    /// axis is on the canvas.
+    fn draw_line(&mut self, from: [i64; 2], to: [i64; 2], color: Color) {
+        let delta = [to[0] - from[0], to[1] - from[1]];
+        let major = usize::from(delta[1].abs() > delta[0].abs());
+        let minor = 1 - major;
+        let (steps, step) = (delta[major].abs(), delta[major].signum());
+        let extent = [self.width(), self.height()][major] as i64;
+
+        // The steps that land on the canvas along the longer axis
+        let (first, last) = if step < 0 {
+            (from[major] - (extent - 1), from[major])
+        } else {
+            (-from[major], extent - 1 - from[major])
+        };
+        for k in first.max(0)..=last.min(steps) {
+            // Bresenham's choice along the shorter axis: the nearest pixel,
+            // rounding halves away from the start
+            let offset = if steps == 0 {
+                0
+            } else {
+                let (k, minor_steps, steps) =
+                    (k as i128, delta[minor].abs() as i128, steps as i128);
+                ((2 * k * minor_steps + steps) / (2 * steps)) as i64
+            };
+            let mut point = [0; 2];
+            point[major] = from[major] + step * k;
+            point[minor] = from[minor] + delta[minor].signum() * offset;
+            self.plot(point[0], point[1], color);
+        }
+    }
+
+    /// Draws the outline of a rectangle whose top left pixel is `(x, y)`.
+    pub fn rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: Color) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let (left, top) = (i64::from(x), i64::from(y));
+        let (right, bottom) = (left + i64::from(width) - 1, top + i64::from(height) - 1);
+        self.draw_line([left, top], [right, top], color);
+        self.draw_line([left, bottom], [right, bottom], color);
+        self.draw_line([left, top], [left, bottom], color);
+        self.draw_line([right, top], [right, bottom], color);
+    }
+
+    /// Fills a rectangle whose top left pixel is `(x, y)`.
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: Color) {
+        let (left, top) = (i64::from(x).max(0), i64::from(y).max(0));
+        let right = (i64::from(x) + i64::from(width)).min(self.width() as i64);
+        let bottom = (i64::from(y) + i64::from(height)).min(self.height() as i64);
+        for row in top..bottom {
+            for col in left..right {
+                self.plot(col, row, color);
+            }
+        }
+    }
+
+    /// Draws the outline of a circle around `(cx, cy)` with the midpoint
+    /// circle algorithm.
+    pub fn circle(&mut self, cx: i32, cy: i32, radius: u32, color: Color) {
+        let (cx, cy, radius) = (i64::from(cx), i64::from(cy), i64::from(radius));
+
+        // Each of the eight points drawn for a step is `y` pixels from the
+        // center along one axis, so only steps whose `y` reaches the canvas
+        // along some axis need visiting
+        let mut ranges = [(cy, self.height()), (cx, self.width())].map(|(center, extent)| {
+            let last = extent as i64 - 1;
+            let near = (-center).max(center - last).max(0);
+            let far = center.abs().max((center - last).abs());
+            (near, far)
+        });
+        ranges.sort();
+
+        let mut next = 0;
+        for (near, far) in ranges {
+            for y in near.max(next)..=far {
+                // The x the midpoint algorithm reaches at this step
+                let squared = i128::from(radius).pow(2) - i128::from(y).pow(2);
+                if squared < 0 {
+                    break;
+                }
+                let x = ((4 * squared as u128).isqrt() as i64 + 1) / 2;
+                if x < y {
+                    break;
+                }
+                for (px, py) in [
+                    (x, y),
+                    (y, x),
+                    (-y, x),
+                    (-x, y),
+                    (-x, -y),
+                    (-y, -x),
+                    (y, -x),
+                    (x, -y),
+                ] {
+                    self.plot(cx + px, cy + py, color);
+                }
+            }
+            next = next.max(far + 1);
+        }
+    }
+
+    /// Renders the canvas into lines, one per row of cells.
+    ///
+    /// Only the colors that change between neighboring cells are emitted, and
+    /// each line ends unstyled.
+    pub fn render(&self) -> Vec<String> {
+        let (cell_width, cell_height) = self.mode.cell_size();
+        (0..self.rows)
+            .map(|row| {
+                let mut previous = Style::new();
+                let cells = (0..self.cols).map(|col| {
+                    // The pixels of the cell, row by row
+                    let pixels: Vec<Option<Color>> = (0..cell_height)
+                        .flat_map(|dy| (0..cell_width).map(move |dx| (dx, dy)))
+                        .map(|(dx, dy)| {
+                            let (x, y) = (col * cell_width + dx, row * cell_height + dy);
+                            self.pixels[y * self.width() + x]
+                        })
+                        .collect();
+                    let (c, mut style) = match self.mode {
+                        CanvasMode::Braille => braille_cell(&pixels),
+                        CanvasMode::HalfBlock => half_block_cell(pixels[0], pixels[1]),
+                        CanvasMode::Quadrant => quadrant_cell(&pixels),
+                    };
+                    // A foreground color doesn't show on a blank cell, so keep
+                    // the previous one rather than switching it off and on
+                    if c == ' ' && previous.bg.is_none() {
+                        style = previous;
+                    }
+                    previous = style;
+                    Piece::Char(c, style)
+                });
+                sgr::render(cells, Style::new())
+            })
+            .collect()
+    }
+}
+
+/// Returns the most common color among the set pixels, preferring the first
+/// one seen on a tie.
+fn dominant(pixels: &[Option<Color>]) -> Style {
+    let set: Vec<Color> = pixels.iter().flatten().copied().collect();
+    let mut best: Option<(Color, usize)> = None;
+    for &color in &set {
+        let count = set.iter().filter(|&&other| other == color).count();
+        if best.is_none_or(|(_, most)| count > most) {
+            best = Some((color, count));
+        }
+    }
+    best.map_or(Style::new(), |(color, _)| Style::new().fg(color))
+}
+
+/// Draws a 2×4 braille cell. The pixels are in rows, left to right.
+fn braille_cell(pixels: &[Option<Color>]) -> (char, Style) {
+    // Braille dot numbers of the pixels, in the same order
+    const BITS: [u32; 8] = [0, 3, 1, 4, 2, 5, 6, 7];
+    let dots = pixels
+        .iter()
+        .zip(BITS)
+        .filter(|(pixel, _)| pixel.is_some())
+        .fold(0, |dots, (_, bit)| dots | 1 << bit);
+    if dots == 0 {
+        return (' ', Style::new());
+    }
+    let c = char::from_u32(0x2800 + dots).unwrap_or(' ');
+    (c, dominant(pixels))
+}
+
+/// Draws a cell of two pixels, one above the other, each in its own color.
+fn half_block_cell(top: Option<Color>, bottom: Option<Color>) -> (char, Style) {
+    match (top, bottom) {
+        (None, None) => (' ', Style::new()),
+        (Some(top), None) => ('▀', Style::new().fg(top)),
+        (None, Some(bottom)) => ('▄', Style::new().fg(bottom)),
+        (Some(top), Some(bottom)) if top == bottom => ('█', Style::new().fg(top)),
+        (Some(top), Some(bottom)) => ('▀', Style::new().fg(top).bg(bottom)),
+    }
+}
+
+/// Draws a 2×2 quadrant cell. The pixels are in rows, left to right.
+fn quadrant_cell(pixels: &[Option<Color>]) -> (char, Style) {
+    let mask = pixels
+        .iter()
+        .enumerate()
+        .filter(|(_, pixel)| pixel.is_some())
+        .fold(0, |mask, (i, _)| mask | 1 << i);
+    (QUADRANTS[mask], dominant(pixels))
+}
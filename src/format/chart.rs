@@ -0,0 +1,280 @@
+use super::{
+    layout::{block_width, pad, HAlign},
+    progress::{EIGHTHS, FULL},
+    sgr::{self, Piece},
+    style::Style,
+};
+use crate::color::{ColorDepth, Gradient};
+
+/// The sparkline characters, from lowest to highest.
+const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Describes how [`Sparkline::render`] draws a series: its colors and the
+/// range of values it spans.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Gradient;
+/// use terminal_style::format::Sparkline;
+///
+/// let latency = [12.0, 15.0, 11.0, 40.0, 13.0];
+/// let spark = Sparkline::new()
+///     .colors(Gradient::new(["#00FF00", "#FFFF00", "#FF0000"])?)
+///     .range(0.0, 50.0);
+/// println!("{}", spark.render(&latency));
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Sparkline {
+    colors: Option<Gradient>,
+    depth: ColorDepth,
+    range: Option<(f64, f64)>,
+}
+
+impl Sparkline {
+    /// Creates an uncolored sparkline spanning the range of its values.
+    pub fn new() -> Sparkline {
+        Sparkline::default()
+    }
+
+    /// Colors each bar by its value: the lowest values take the start of
+    /// `colors` and the highest its end.
+    pub fn colors(mut self, colors: impl Into<Gradient>) -> Sparkline {
+        self.colors = Some(colors.into());
+        self
+    }
+
+    /// Sets whether the colors are TrueColor or from the 256-color palette.
+    ///
+    /// Defaults to [`ColorDepth::TrueColor`].
+    pub fn color_depth(mut self, depth: ColorDepth) -> Sparkline {
+        self.depth = depth;
+        self
+    }
+
+    /// Fixes the values drawn as the lowest and highest bars, instead of
+    /// using the smallest and largest values in the series. Values outside
+    /// the range are clamped.
+    pub fn range(mut self, min: f64, max: f64) -> Sparkline {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Renders a series of values. See [`sparkline`].
+    pub fn render(&self, values: &[f64]) -> String {
+        let finite = values.iter().copied().filter(|value| value.is_finite());
+        let (min, max) = self.range.unwrap_or_else(|| {
+            finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            })
+        });
+
+        let pieces = values.iter().map(|&value| {
+            if !value.is_finite() {
+                return Piece::Char(' ', Style::new());
+            }
+            // A flat series has nothing to compare against, so sits in the middle
+            let t = if max > min {
+                ((value - min) / (max - min)).clamp(0.0, 1.0)
+            } else {
+                0.5
+            };
+            let style = match &self.colors {
+                Some(colors) => Style::new().fg(self.depth.color(colors.at(t))),
+                None => Style::new(),
+            };
+            Piece::Char(LEVELS[(t * 7.0).round() as usize], style)
+        });
+        sgr::render(pieces, Style::new())
+    }
+}
+
+/// Draws a series of values as a row of bars of increasing height, one
+/// character per value.
+///
+/// The smallest value is drawn as `▁` and the largest as `█`. Values that are
+/// not finite, such as `NaN` for a missing sample, are left as gaps. Use
+/// [`Sparkline`] to color the bars or fix the range.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::sparkline;
+///
+/// assert_eq!(sparkline(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]), "▁▂▃▄▅▆▇█");
+/// assert_eq!(sparkline(&[0.0, f64::NAN, 10.0]), "▁ █");
+/// ```
+pub fn sparkline(values: &[f64]) -> String {
+    Sparkline::new().render(values)
+}
+
+/// Describes how [`BarChart::render`] draws labeled bars: their colors, scale
+/// and values.
+///
+/// By default the bars are uncolored, the longest bar fills the chart's
+/// width, and each bar is followed by its value.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Gradient;
+/// use terminal_style::format::BarChart;
+///
+/// let chart = BarChart::new()
+///     .colors(Gradient::new(["#00FF00", "#FF0000"])?)
+///     .max(100.0)
+///     .precision(1);
+/// let lines = chart.render(&[("p50", 12.0), ("p99", 87.5)], 20);
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarChart {
+    colors: Option<Gradient>,
+    depth: ColorDepth,
+    max: Option<f64>,
+    values: bool,
+    precision: Option<usize>,
+}
+
+impl Default for BarChart {
+    fn default() -> Self {
+        BarChart {
+            colors: None,
+            depth: ColorDepth::TrueColor,
+            max: None,
+            values: true,
+            precision: None,
+        }
+    }
+}
+
+impl BarChart {
+    /// Creates the default bar chart options.
+    pub fn new() -> BarChart {
+        BarChart::default()
+    }
+
+    /// Colors each bar by its value, from the start of `colors` for zero to
+    /// its end for the largest value.
+    pub fn colors(mut self, colors: impl Into<Gradient>) -> BarChart {
+        self.colors = Some(colors.into());
+        self
+    }
+
+    /// Sets whether the colors are TrueColor or from the 256-color palette.
+    ///
+    /// Defaults to [`ColorDepth::TrueColor`].
+    pub fn color_depth(mut self, depth: ColorDepth) -> BarChart {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the value drawn as a full-width bar, instead of the largest value.
+    ///
+    /// Larger values are cut off at the full width.
+    pub fn max(mut self, max: f64) -> BarChart {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets whether each bar is followed by its value.
+    pub fn values(mut self, show: bool) -> BarChart {
+        self.values = show;
+        self
+    }
+
+    /// Shows values with `digits` digits after the decimal point.
+    pub fn precision(mut self, digits: usize) -> BarChart {
+        self.precision = Some(digits);
+        self
+    }
+
+    /// Renders labeled values as bars. See [`bar_chart`].
+    pub fn render<L: AsRef<str>>(&self, data: &[(L, f64)], width: usize) -> Vec<String> {
+        let labels: Vec<&str> = data.iter().map(|(label, _)| label.as_ref()).collect();
+        let label_width = block_width(&labels);
+        let max = self.max.unwrap_or_else(|| {
+            data.iter()
+                .map(|&(_, value)| value)
+                .filter(|value| value.is_finite())
+                .fold(0.0, f64::max)
+        });
+
+        data.iter()
+            .zip(&labels)
+            .map(|(&(_, value), label)| {
+                let t = if max > 0.0 && value.is_finite() {
+                    (value / max).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let mut line = format!(
+                    "{} {}",
+                    pad(label, label_width, HAlign::Left),
+                    self.bar(t, width)
+                );
+                if self.values {
+                    line.push(' ');
+                    line.push_str(&match self.precision {
+                        Some(digits) => format!("{:.*}", digits, value),
+                        None => value.to_string(),
+                    });
+                }
+                line
+            })
+            .collect()
+    }
+
+    /// Draws one bar `t` of the way across `width` columns. Bars are padded
+    /// to the full width when values follow them, so the values line up.
+    fn bar(&self, t: f64, width: usize) -> String {
+        let eighths = (t * width as f64 * 8.0).round() as usize;
+        let (full, partial) = (eighths / 8, eighths % 8);
+        let mut bar: String = std::iter::repeat_n(FULL, full).collect();
+        if partial > 0 {
+            bar.push(EIGHTHS[partial - 1]);
+        }
+        let length = bar.chars().count();
+
+        if let (Some(colors), false) = (&self.colors, bar.is_empty()) {
+            bar = Style::new().fg(self.depth.color(colors.at(t))).paint(&bar);
+        }
+        if self.values {
+            bar.push_str(&" ".repeat(width - length));
+        }
+        bar
+    }
+}
+
+/// Renders labeled values as horizontal bars, one line per value.
+///
+/// Labels are aligned to the widest one, measured by visible width, so they
+/// may already be styled. The largest value fills `width` columns and the
+/// others are scaled to it, with a precision of an eighth of a column.
+/// Negative values get an empty bar. Each bar is followed by its value.
+/// Use [`BarChart`] to color the bars, fix the scale or hide the values.
+///
+/// # Arguments
+///
+/// * `data` - Pairs of a label and a value.
+/// * `width` - The width of the longest bar, in columns.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::bar_chart;
+///
+/// let chart = bar_chart(&[("get", 40.0), ("post", 25.0), ("delete", 5.0)], 8);
+/// assert_eq!(
+///     chart,
+///     vec![
+///         "get    ████████ 40",
+///         "post   █████    25",
+///         "delete █        5",
+///     ]
+/// );
+/// ```
+pub fn bar_chart<L: AsRef<str>>(data: &[(L, f64)], width: usize) -> Vec<String> {
+    BarChart::new().render(data, width)
+}
@@ -1,5 +1,7 @@
 use super::stylable::Stylable;
-use crate::color::{rgb_from_color_definition, rgb_to_ansi8, ColorConversionError, IntoRgb};
+use crate::color::{
+    rgb_from_color_definition, rgb_to_ansi8, ColorConversionError, IntoColor, IntoRgb,
+};
 
 /// Applies a foreground color using **24-bit TrueColor** ANSI sequences.
 ///
@@ -137,6 +139,74 @@ where
     text.apply_result(f)
 }
 
+/// Applies a foreground color in the **same form it was specified** in.
+///
+/// Unlike [`color_rgb`] and [`color_ansi`], no conversion takes place: a
+/// [`Color::Ansi16`](crate::color::Color::Ansi16) value is emitted as `\x1b[3Xm`,
+/// an `Ansi256` value as `\x1b[38;5;Nm` and an `Rgb` value as `\x1b[38;2;R;G;Bm`.
+///
+/// # Arguments
+///
+/// * `color_input` - Any type implementing [`IntoColor`] (e.g., [`Color`](crate::color::Color), `"ansi:4"`, `"red"`, `#RRGGBB`).
+/// * `text` - Any type implementing [`Stylable`].
+///
+/// # Errors
+///
+/// Returns [`ColorConversionError`] if the color input is invalid.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::color_native;
+///
+/// let s = color_native("ansi:4", "Theme Blue").unwrap();
+/// assert_eq!(s, "\x1b[34mTheme Blue\x1b[0m");
+/// ```
+pub fn color_native<C, T>(color_input: C, text: T) -> Result<T::Output, ColorConversionError>
+where
+    C: Copy + IntoColor,
+    T: Stylable,
+{
+    let f = |s: &str| -> Result<String, ColorConversionError> {
+        let color = color_input.into_color()?;
+        Ok(format!("{}{}\x1b[0m", color.fg_sequence(), s))
+    };
+    text.apply_result(f)
+}
+
+/// Applies a background color in the **same form it was specified** in.
+///
+/// See [`color_native`] for how each kind of color is emitted.
+///
+/// # Arguments
+///
+/// * `color_input` - Any type implementing [`IntoColor`].
+/// * `text` - Any type implementing [`Stylable`].
+///
+/// # Errors
+///
+/// Returns [`ColorConversionError`] if the color input is invalid.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::background_native;
+///
+/// let s = background_native("bright-red", "Alert").unwrap();
+/// assert_eq!(s, "\x1b[101mAlert\x1b[0m");
+/// ```
+pub fn background_native<C, T>(color_input: C, text: T) -> Result<T::Output, ColorConversionError>
+where
+    C: Copy + IntoColor,
+    T: Stylable,
+{
+    let f = |s: &str| -> Result<String, ColorConversionError> {
+        let color = color_input.into_color()?;
+        Ok(format!("{}{}\x1b[0m", color.bg_sequence(), s))
+    };
+    text.apply_result(f)
+}
+
 /// Applies a foreground color using the default format (**TrueColor**).
 ///
 /// Alias for [`color_rgb`].
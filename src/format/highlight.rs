@@ -0,0 +1,160 @@
+use std::ops::Range;
+
+use super::{sgr, stylable::Stylable, style::Style};
+
+/// A pattern that [`highlight`] can search for.
+///
+/// Mirrors the patterns accepted by [`str::find`]: string literals, single
+/// characters, sets of characters and `Fn(char) -> bool` predicates. With the
+/// `regex` feature enabled, [`regex::Regex`] is supported as well. Custom
+/// matchers can implement the trait directly.
+pub trait HighlightPattern {
+    /// Returns the byte ranges of all non-overlapping matches in `haystack`,
+    /// in ascending order.
+    fn find_matches(&self, haystack: &str) -> Vec<Range<usize>>;
+}
+
+/// Matches every non-overlapping occurrence of the string. An empty needle matches nothing.
+impl HighlightPattern for &str {
+    fn find_matches(&self, haystack: &str) -> Vec<Range<usize>> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        haystack
+            .match_indices(*self)
+            .map(|(start, m)| start..start + m.len())
+            .collect()
+    }
+}
+
+/// Matches every non-overlapping occurrence of the string. An empty needle matches nothing.
+impl HighlightPattern for String {
+    fn find_matches(&self, haystack: &str) -> Vec<Range<usize>> {
+        self.as_str().find_matches(haystack)
+    }
+}
+
+/// Matches every non-overlapping occurrence of the string. An empty needle matches nothing.
+impl HighlightPattern for &String {
+    fn find_matches(&self, haystack: &str) -> Vec<Range<usize>> {
+        self.as_str().find_matches(haystack)
+    }
+}
+
+/// Matches every occurrence of the character, merging adjacent ones.
+impl HighlightPattern for char {
+    fn find_matches(&self, haystack: &str) -> Vec<Range<usize>> {
+        char_runs(haystack, |c| c == *self)
+    }
+}
+
+/// Matches runs of characters from the set.
+impl<const N: usize> HighlightPattern for [char; N] {
+    fn find_matches(&self, haystack: &str) -> Vec<Range<usize>> {
+        char_runs(haystack, |c| self.contains(&c))
+    }
+}
+
+/// Matches runs of characters from the set.
+impl HighlightPattern for &[char] {
+    fn find_matches(&self, haystack: &str) -> Vec<Range<usize>> {
+        char_runs(haystack, |c| self.contains(&c))
+    }
+}
+
+/// Matches runs of characters for which the predicate returns `true`.
+impl<F> HighlightPattern for F
+where
+    F: Fn(char) -> bool,
+{
+    fn find_matches(&self, haystack: &str) -> Vec<Range<usize>> {
+        char_runs(haystack, self)
+    }
+}
+
+/// Matches every non-overlapping match of the regular expression.
+#[cfg(feature = "regex")]
+impl HighlightPattern for regex::Regex {
+    fn find_matches(&self, haystack: &str) -> Vec<Range<usize>> {
+        self.find_iter(haystack)
+            .map(|m| m.range())
+            .filter(|range| !range.is_empty())
+            .collect()
+    }
+}
+
+/// Matches every non-overlapping match of the regular expression.
+#[cfg(feature = "regex")]
+impl HighlightPattern for &regex::Regex {
+    fn find_matches(&self, haystack: &str) -> Vec<Range<usize>> {
+        (*self).find_matches(haystack)
+    }
+}
+
+/// Returns the byte ranges of maximal runs of characters matching `predicate`.
+fn char_runs<F: Fn(char) -> bool>(haystack: &str, predicate: F) -> Vec<Range<usize>> {
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    for (start, c) in haystack.char_indices() {
+        if !predicate(c) {
+            continue;
+        }
+        let end = start + c.len_utf8();
+        match runs.last_mut() {
+            Some(run) if run.end == start => run.end = end,
+            _ => runs.push(start..end),
+        }
+    }
+    runs
+}
+
+/// Styles every match of `pattern` inside a string, the way `grep --color` does.
+///
+/// Matching is done against the visible text, so escape sequences already in
+/// the input never prevent a match. Matched characters keep their existing
+/// styling with `style` layered on top (see [`Style::patch`]), and the rest of
+/// the text is left as it was.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{bold, highlight, Style};
+///
+/// let line = highlight("error: disk full", "disk", Style::new().inverse());
+/// assert_eq!(line, "error: \x1b[7mdisk\x1b[0m full");
+///
+/// let lines = vec!["id 42", "no digits"];
+/// let digits = highlight(lines, |c: char| c.is_ascii_digit(), Style::new().underline());
+/// assert_eq!(digits, vec!["id \x1b[4m42\x1b[0m", "no digits"]);
+///
+/// // Already-styled text keeps its styling around and under the match
+/// let styled = highlight(bold("a needle here"), "needle", Style::new().underline());
+/// assert_eq!(styled, "\x1b[1ma \x1b[4mneedle\x1b[24m here\x1b[0m");
+/// ```
+pub fn highlight<T, P>(text: T, pattern: P, style: Style) -> T::Output
+where
+    T: Stylable,
+    P: HighlightPattern,
+{
+    text.apply(|s| highlight_str(s, &pattern, style))
+}
+
+/// Highlights the matches of `pattern` in a single string.
+fn highlight_str<P: HighlightPattern>(s: &str, pattern: &P, style: Style) -> String {
+    let visible = sgr::strip(s);
+    let matches = pattern.find_matches(&visible);
+    if matches.is_empty() {
+        return s.to_string();
+    }
+
+    // Walk the visible characters in step with their byte offsets in `visible`.
+    let mut offsets = visible.char_indices().map(|(offset, _)| offset);
+    let mut matches = matches.into_iter().peekable();
+    sgr::restyle(s, |_, _, current| {
+        let offset = offsets.next().unwrap_or(visible.len());
+        while matches.next_if(|m| m.end <= offset).is_some() {}
+        match matches.peek() {
+            Some(m) if m.contains(&offset) => current.patch(&style),
+            _ => current,
+        }
+    })
+}
@@ -0,0 +1,336 @@
+//! Rendering of raw RGB images as half-block characters.
+//!
+//! Each terminal cell shows two pixels, one above the other: the upper one
+//! is the foreground color of a `▀` and the lower one its background.
+//!
+//! [`ImageData`] describes images for the terminal graphics protocols in
+//! [`kitty`](super::kitty) and [`iterm2`](super::iterm2).
+
+use super::{
+    png,
+    sgr::{self, Piece},
+    style::Style,
+};
+use crate::color::{
+    dither::check_buffer, dither_grid, Color, ColorConversionError, ColorDepth, Dither, Palette,
+};
+
+/// The character whose foreground fills the upper half of a cell.
+const UPPER_HALF: char = '▀';
+
+/// An image to send to the terminal: raw pixels, or an encoded PNG file.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::image::ImageData;
+///
+/// let pixels = [255, 0, 0, 0, 0, 255];
+/// let image = ImageData::Rgb { width: 2, height: 1, pixels: &pixels };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageData<'a> {
+    /// 8-bit RGB pixels, row by row from the top left, as `r, g, b` bytes.
+    Rgb {
+        width: usize,
+        height: usize,
+        pixels: &'a [u8],
+    },
+    /// 8-bit RGBA pixels, row by row from the top left, as `r, g, b, a` bytes.
+    Rgba {
+        width: usize,
+        height: usize,
+        pixels: &'a [u8],
+    },
+    /// The contents of a PNG file.
+    Png(&'a [u8]),
+}
+
+impl ImageData<'_> {
+    /// Checks that raw pixel data has the right length for its size, and
+    /// that the size fits the 32-bit fields of the image formats.
+    pub(crate) fn check(&self) -> Result<(), ColorConversionError> {
+        let (width, height, channels, pixels) = match *self {
+            ImageData::Rgb {
+                width,
+                height,
+                pixels,
+            } => (width, height, 3, pixels),
+            ImageData::Rgba {
+                width,
+                height,
+                pixels,
+            } => (width, height, 4, pixels),
+            ImageData::Png(_) => return Ok(()),
+        };
+        if u32::try_from(width).is_err() || u32::try_from(height).is_err() {
+            return Err(ColorConversionError::InvalidRgb(format!(
+                "a {}x{} image is too large",
+                width, height
+            )));
+        }
+        check_buffer(width, height, channels, pixels)
+    }
+
+    /// Returns the image as a PNG file, encoding raw pixels if needed.
+    /// The image must have passed [`check`](Self::check), so its size fits a `u32`.
+    pub(crate) fn to_png(self) -> Vec<u8> {
+        match self {
+            ImageData::Rgb {
+                width,
+                height,
+                pixels,
+            } => png::encode(width as u32, height as u32, 3, pixels),
+            ImageData::Rgba {
+                width,
+                height,
+                pixels,
+            } => png::encode(width as u32, height as u32, 4, pixels),
+            ImageData::Png(png) => png.to_vec(),
+        }
+    }
+}
+
+/// Describes how [`render`] fits an image to the terminal: its colors,
+/// dithering and the shape of terminal cells.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::{ColorDepth, Dither};
+/// use terminal_style::format::image::ImageOptions;
+///
+/// let options = ImageOptions::new()
+///     .color_depth(ColorDepth::Ansi256)
+///     .dither(Dither::FloydSteinberg)
+///     .cell_aspect(2.2);
+/// let lines = options.render(2, 2, &[255; 12], 2)?;
+/// assert_eq!(lines, vec!["\x1b[38;5;231;48;5;231m▀▀\x1b[0m"]);
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageOptions {
+    depth: ColorDepth,
+    dither: Dither,
+    cell_aspect: f64,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        ImageOptions {
+            depth: ColorDepth::TrueColor,
+            dither: Dither::None,
+            cell_aspect: 2.0,
+        }
+    }
+}
+
+impl ImageOptions {
+    /// Creates the default options: TrueColor, for cells twice as tall as they are wide.
+    pub fn new() -> ImageOptions {
+        ImageOptions::default()
+    }
+
+    /// Sets whether pixels are drawn in TrueColor, like
+    /// [`color_rgb`](crate::format::color_rgb) and
+    /// [`background_rgb`](crate::format::background_rgb), or with the
+    /// 256-color palette, like [`color_ansi`](crate::format::color_ansi) and
+    /// [`background_ansi`](crate::format::background_ansi).
+    pub fn color_depth(mut self, depth: ColorDepth) -> ImageOptions {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets how colors are dithered when drawing with the 256-color palette,
+    /// to avoid banding in smooth areas. TrueColor images are not dithered.
+    pub fn dither(mut self, dither: Dither) -> ImageOptions {
+        self.dither = dither;
+        self
+    }
+
+    /// Sets the height of a terminal cell divided by its width, so the image
+    /// keeps its proportions. Most terminal fonts are close to `2.0`, the default.
+    ///
+    /// Values are clamped to between `0.1` and `10.0`. Zero, negative and
+    /// non-finite values are ignored.
+    pub fn cell_aspect(mut self, aspect: f64) -> ImageOptions {
+        if aspect.is_finite() && aspect > 0.0 {
+            self.cell_aspect = aspect.clamp(0.1, 10.0);
+        }
+        self
+    }
+
+    /// Renders an image. See [`render`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColorConversionError::InvalidRgb`] if `rgb` is not
+    /// `width * height * 3` bytes long, or if the scaled image would be too
+    /// large to hold in memory.
+    pub fn render(
+        &self,
+        width: usize,
+        height: usize,
+        rgb: &[u8],
+        target_cols: usize,
+    ) -> Result<Vec<String>, ColorConversionError> {
+        check_buffer(width, height, 3, rgb)?;
+        if width == 0 || height == 0 || target_cols == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Each cell is one pixel wide and two tall, so at the default aspect
+        // of 2.0 the pixels are square
+        let pixel_rows = height as f64 * target_cols as f64 / width as f64 * 2.0 / self.cell_aspect;
+        let pixel_rows = (pixel_rows.round() as usize).max(1);
+        // Resampling holds a grid of `target_cols` float pixels for every
+        // source and target row, so that must fit in memory
+        target_cols
+            .checked_mul(pixel_rows.max(height))
+            .and_then(|n| n.checked_mul(std::mem::size_of::<[f64; 3]>()))
+            .filter(|&bytes| bytes <= isize::MAX as usize)
+            .ok_or_else(|| {
+                ColorConversionError::InvalidRgb(format!(
+                    "a {}x{} image is too large to scale to {} columns",
+                    width, height, target_cols
+                ))
+            })?;
+        let pixels = resample(width, height, rgb, target_cols, pixel_rows);
+        let colors: Vec<Color> = match (self.depth, self.dither) {
+            (ColorDepth::Ansi256, dither) if dither != Dither::None => {
+                let rows: Vec<&[[u8; 3]]> = pixels.chunks(target_cols).collect();
+                dither_grid(&rows, &Palette::xterm256(), dither)
+                    .into_iter()
+                    .flatten()
+                    .map(Color::Ansi256)
+                    .collect()
+            }
+            (depth, _) => pixels.iter().map(|&rgb| depth.color(rgb)).collect(),
+        };
+
+        let lines = (0..pixel_rows)
+            .step_by(2)
+            .map(|top| {
+                let cells = (0..target_cols).map(|x| {
+                    let upper = colors[top * target_cols + x];
+                    let style = match colors.get((top + 1) * target_cols + x) {
+                        Some(&lower) => Style::new().fg(upper).bg(lower),
+                        None => Style::new().fg(upper),
+                    };
+                    Piece::Char(UPPER_HALF, style)
+                });
+                sgr::render(cells, Style::new())
+            })
+            .collect();
+        Ok(lines)
+    }
+}
+
+/// For each pixel of a resized line, the source pixels it covers and how
+/// much each contributes. The weights of each pixel add up to 1.
+fn area_weights(from: usize, to: usize) -> Vec<Vec<(usize, f64)>> {
+    let scale = from as f64 / to as f64;
+    (0..to)
+        .map(|i| {
+            let (start, end) = (i as f64 * scale, (i + 1) as f64 * scale);
+            let first = start.floor() as usize;
+            let last = (end.ceil() as usize).min(from);
+            (first..last)
+                .map(|source| {
+                    let overlap = end.min(source as f64 + 1.0) - start.max(source as f64);
+                    (source, overlap / scale)
+                })
+                .filter(|&(_, weight)| weight > 0.0)
+                .collect()
+        })
+        .collect()
+}
+
+/// Resizes an RGB image by area averaging: each new pixel is the average
+/// of the source pixels it covers, weighted by how much of each it covers.
+///
+/// `rgb` must hold `width * height` RGB triples, row by row.
+pub(crate) fn resample(
+    width: usize,
+    height: usize,
+    rgb: &[u8],
+    new_width: usize,
+    new_height: usize,
+) -> Vec<[u8; 3]> {
+    let columns = area_weights(width, new_width);
+    let rows = area_weights(height, new_height);
+
+    // Resize each row horizontally, then the result vertically
+    let mut wide = vec![[0.0f64; 3]; new_width * height];
+    for y in 0..height {
+        for (x, weights) in columns.iter().enumerate() {
+            let pixel = &mut wide[y * new_width + x];
+            for &(source, weight) in weights {
+                let offset = (y * width + source) * 3;
+                for channel in 0..3 {
+                    pixel[channel] += rgb[offset + channel] as f64 * weight;
+                }
+            }
+        }
+    }
+
+    let mut out = vec![[0u8; 3]; new_width * new_height];
+    for (y, weights) in rows.iter().enumerate() {
+        for x in 0..new_width {
+            let mut sum = [0.0f64; 3];
+            for &(source, weight) in weights {
+                let pixel = wide[source * new_width + x];
+                for channel in 0..3 {
+                    sum[channel] += pixel[channel] * weight;
+                }
+            }
+            out[y * new_width + x] = sum.map(|value| value.round().clamp(0.0, 255.0) as u8);
+        }
+    }
+    out
+}
+
+/// Renders a raw RGB image as lines of `▀` half blocks, scaled to
+/// `target_cols` columns.
+///
+/// Each cell shows two pixels: the upper in its foreground color and the
+/// lower in its background. The image is resized by area averaging, so
+/// detail is blended rather than dropped when shrinking, and the number of
+/// rows is chosen to keep its proportions. Use [`ImageOptions`] for the
+/// 256-color palette or cells of a different shape.
+///
+/// # Arguments
+///
+/// * `width` - The width of the image, in pixels.
+/// * `height` - The height of the image, in pixels.
+/// * `rgb` - The pixels, row by row from the top left, as `r, g, b` bytes.
+/// * `target_cols` - The number of terminal columns to fit the image to.
+///
+/// # Errors
+///
+/// Returns [`ColorConversionError::InvalidRgb`] if `rgb` is not
+/// `width * height * 3` bytes long, or if the scaled image would be too
+/// large to hold in memory.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::image;
+///
+/// // A 2×2 image: red and green above, blue and white below
+/// let pixels = [255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255];
+/// let lines = image::render(2, 2, &pixels, 2)?;
+/// assert_eq!(
+///     lines,
+///     vec!["\x1b[38;2;255;0;0;48;2;0;0;255m▀\x1b[38;2;0;255;0;48;2;255;255;255m▀\x1b[0m"]
+/// );
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+pub fn render(
+    width: usize,
+    height: usize,
+    rgb: &[u8],
+    target_cols: usize,
+) -> Result<Vec<String>, ColorConversionError> {
+    ImageOptions::new().render(width, height, rgb, target_cols)
+}
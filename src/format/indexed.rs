@@ -0,0 +1,363 @@
+use std::{borrow::Cow, convert::Infallible, rc::Rc, sync::Arc};
+
+use super::style::Style;
+
+/// The position of an element passed to [`StylableIndexed::apply_indexed`].
+///
+/// Flat sequences are indexed by `usize` and treated as a single column;
+/// grids are indexed by `(row, col)`.
+pub trait CellIndex: Copy {
+    /// The row of the element.
+    fn row(&self) -> usize;
+
+    /// The column of the element.
+    fn col(&self) -> usize;
+}
+
+impl CellIndex for usize {
+    fn row(&self) -> usize {
+        *self
+    }
+
+    fn col(&self) -> usize {
+        0
+    }
+}
+
+impl CellIndex for (usize, usize) {
+    fn row(&self) -> usize {
+        self.0
+    }
+
+    fn col(&self) -> usize {
+        self.1
+    }
+}
+
+/// A trait for applying styling that depends on where an element sits.
+///
+/// Where [`Stylable`](super::Stylable) hands its closure only the text, the
+/// closure here also receives the element's position: an `index` for flat
+/// sequences (`Vec<String>`, `&[&str]`, `[Cow<str>; 3]`, ...) and a
+/// `(row, col)` pair for grids (`Vec<Vec<String>>`, `[[&str; 2]; 2]`,
+/// `Vec<&[Box<str>]>`, ...). The elements are [`IndexedItem`]s.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{bold, StylableIndexed};
+///
+/// let grid = vec![vec!["a", "b"], vec!["c", "d"]];
+/// let diagonal = grid.apply_indexed(|(row, col), s| {
+///     if row == col { bold(s) } else { s.to_string() }
+/// });
+/// assert_eq!(diagonal[1][1], "\x1b[1md\x1b[0m");
+/// assert_eq!(diagonal[1][0], "c");
+/// ```
+pub trait StylableIndexed {
+    /// The position handed to the closure: `usize` or `(usize, usize)`.
+    type Index: CellIndex;
+
+    /// The resulting type after styling is applied.
+    type Output;
+
+    /// Applies a non-failing styling closure to each element, along with its position.
+    fn apply_indexed<F>(&self, f: F) -> Self::Output
+    where
+        F: Fn(Self::Index, &str) -> String;
+
+    /// Applies a fallible styling closure to each element, along with its position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any single application of the closure fails.
+    fn apply_indexed_result<F, E>(&self, f: F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+        E: std::fmt::Debug;
+}
+
+/// An element of a sequence styled with [`StylableIndexed`]: a piece of
+/// text, or a row of text in a grid.
+///
+/// Text is `str`, `String`, and references, smart pointers and `Cow`s of
+/// them. Rows are slices, arrays and `Vec`s of anything that is
+/// `AsRef<str>`.
+pub trait IndexedItem {
+    /// The position handed to the closure: `usize` for text, `(usize, usize)`
+    /// for the cells of a row.
+    type Index: CellIndex;
+
+    /// The resulting type after styling is applied.
+    type Output;
+
+    /// Applies a styling closure to the item, which sits at `row` of its sequence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any single application of the closure fails.
+    fn apply_row<F, E>(&self, row: usize, f: &F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>;
+}
+
+impl IndexedItem for str {
+    type Index = usize;
+    type Output = String;
+
+    fn apply_row<F, E>(&self, row: usize, f: &F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+    {
+        f(row, self)
+    }
+}
+
+impl IndexedItem for String {
+    type Index = usize;
+    type Output = String;
+
+    fn apply_row<F, E>(&self, row: usize, f: &F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+    {
+        f(row, self)
+    }
+}
+
+/// Implements forwarding from a pointer type to the `IndexedItem` it points to.
+macro_rules! forward_indexed_item {
+    ($($pointer:ty),* $(,)?) => {
+        $(
+            impl<T: IndexedItem + ?Sized> IndexedItem for $pointer {
+                type Index = T::Index;
+                type Output = T::Output;
+
+                fn apply_row<F, E>(&self, row: usize, f: &F) -> Result<Self::Output, E>
+                where
+                    F: Fn(Self::Index, &str) -> Result<String, E>,
+                {
+                    (**self).apply_row(row, f)
+                }
+            }
+        )*
+    };
+}
+
+forward_indexed_item!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
+impl<B> IndexedItem for Cow<'_, B>
+where
+    B: IndexedItem + ToOwned + ?Sized,
+{
+    type Index = B::Index;
+    type Output = B::Output;
+
+    fn apply_row<F, E>(&self, row: usize, f: &F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+    {
+        (**self).apply_row(row, f)
+    }
+}
+
+impl<T: AsRef<str>> IndexedItem for [T] {
+    type Index = (usize, usize);
+    type Output = Vec<String>;
+
+    fn apply_row<F, E>(&self, row: usize, f: &F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+    {
+        self.iter()
+            .enumerate()
+            .map(|(col, cell)| f((row, col), cell.as_ref()))
+            .collect()
+    }
+}
+
+impl<T: AsRef<str>> IndexedItem for Vec<T> {
+    type Index = (usize, usize);
+    type Output = Vec<String>;
+
+    fn apply_row<F, E>(&self, row: usize, f: &F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+    {
+        self.as_slice().apply_row(row, f)
+    }
+}
+
+impl<T: AsRef<str>, const N: usize> IndexedItem for [T; N] {
+    type Index = (usize, usize);
+    type Output = Vec<String>;
+
+    fn apply_row<F, E>(&self, row: usize, f: &F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+    {
+        self.as_slice().apply_row(row, f)
+    }
+}
+
+// --- Implement StylableIndexed for sequences of items ---
+
+impl<T: IndexedItem> StylableIndexed for [T] {
+    type Index = T::Index;
+    type Output = Vec<T::Output>;
+
+    fn apply_indexed<F>(&self, f: F) -> Self::Output
+    where
+        F: Fn(Self::Index, &str) -> String,
+    {
+        let infallible = |index, s: &str| Ok::<_, Infallible>(f(index, s));
+        match self.apply_indexed_result(infallible) {
+            Ok(output) => output,
+            Err(never) => match never {},
+        }
+    }
+
+    fn apply_indexed_result<F, E>(&self, f: F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+        E: std::fmt::Debug,
+    {
+        self.iter()
+            .enumerate()
+            .map(|(row, item)| item.apply_row(row, &f))
+            .collect()
+    }
+}
+
+// Forward Vec<T>, arrays and references to the slice implementation
+impl<T> StylableIndexed for Vec<T>
+where
+    [T]: StylableIndexed,
+{
+    type Index = <[T] as StylableIndexed>::Index;
+    type Output = <[T] as StylableIndexed>::Output;
+
+    fn apply_indexed<F>(&self, f: F) -> Self::Output
+    where
+        F: Fn(Self::Index, &str) -> String,
+    {
+        self.as_slice().apply_indexed(f)
+    }
+
+    fn apply_indexed_result<F, E>(&self, f: F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+        E: std::fmt::Debug,
+    {
+        self.as_slice().apply_indexed_result(f)
+    }
+}
+
+impl<T, const N: usize> StylableIndexed for [T; N]
+where
+    [T]: StylableIndexed,
+{
+    type Index = <[T] as StylableIndexed>::Index;
+    type Output = <[T] as StylableIndexed>::Output;
+
+    fn apply_indexed<F>(&self, f: F) -> Self::Output
+    where
+        F: Fn(Self::Index, &str) -> String,
+    {
+        self.as_slice().apply_indexed(f)
+    }
+
+    fn apply_indexed_result<F, E>(&self, f: F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+        E: std::fmt::Debug,
+    {
+        self.as_slice().apply_indexed_result(f)
+    }
+}
+
+impl<T: StylableIndexed + ?Sized> StylableIndexed for &T {
+    type Index = T::Index;
+    type Output = T::Output;
+
+    fn apply_indexed<F>(&self, f: F) -> Self::Output
+    where
+        F: Fn(Self::Index, &str) -> String,
+    {
+        (**self).apply_indexed(f)
+    }
+
+    fn apply_indexed_result<F, E>(&self, f: F) -> Result<Self::Output, E>
+    where
+        F: Fn(Self::Index, &str) -> Result<String, E>,
+        E: std::fmt::Debug,
+    {
+        (**self).apply_indexed_result(f)
+    }
+}
+
+// --- Convenience functions ---
+
+/// Styles rows alternately with `even` and `odd`, starting with `even` on row 0.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{stripe_rows, Style};
+///
+/// let grid = vec![vec!["a", "b"], vec!["c", "d"]];
+/// let striped = stripe_rows(Style::new().bold(), Style::new().faint(), &grid);
+/// assert_eq!(striped[0][1], "\x1b[1mb\x1b[0m");
+/// assert_eq!(striped[1][0], "\x1b[2mc\x1b[0m");
+/// ```
+pub fn stripe_rows<G: StylableIndexed>(even: Style, odd: Style, grid: G) -> G::Output {
+    grid.apply_indexed(|index, s| {
+        if index.row() % 2 == 0 {
+            even.paint(s)
+        } else {
+            odd.paint(s)
+        }
+    })
+}
+
+/// Styles every element in row `n`, leaving the rest unchanged.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{style_row, Style};
+///
+/// let lines = vec!["first", "second"];
+/// let styled = style_row(1, Style::new().underline(), &lines);
+/// assert_eq!(styled, vec!["first", "\x1b[4msecond\x1b[0m"]);
+/// ```
+pub fn style_row<G: StylableIndexed>(n: usize, style: Style, grid: G) -> G::Output {
+    grid.apply_indexed(|index, s| {
+        if index.row() == n {
+            style.paint(s)
+        } else {
+            s.to_string()
+        }
+    })
+}
+
+/// Styles every cell in column `n`, leaving the rest unchanged.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{style_column, Style};
+///
+/// let grid = vec![vec!["id", "name"], vec!["1", "ada"]];
+/// let styled = style_column(0, Style::new().bold(), &grid);
+/// assert_eq!(styled[1], vec!["\x1b[1m1\x1b[0m", "ada"]);
+/// ```
+pub fn style_column<G: StylableIndexed>(n: usize, style: Style, grid: G) -> G::Output {
+    grid.apply_indexed(|index, s| {
+        if index.col() == n {
+            style.paint(s)
+        } else {
+            s.to_string()
+        }
+    })
+}
@@ -0,0 +1,90 @@
+use std::iter::FusedIterator;
+
+use super::{style::Style, Stylable};
+
+/// An iterator adapter that paints each item with a [`Style`] lazily, as it
+/// is produced.
+///
+/// Created by [`StyledIterator::styled`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Styled<I> {
+    iter: I,
+    style: Style,
+}
+
+impl<I> Iterator for Styled<I>
+where
+    I: Iterator,
+    I::Item: Stylable,
+{
+    type Item = <I::Item as Stylable>::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|item| self.style.apply(item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for Styled<I>
+where
+    I: DoubleEndedIterator,
+    I::Item: Stylable,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|item| self.style.apply(item))
+    }
+}
+
+impl<I> ExactSizeIterator for Styled<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Stylable,
+{
+}
+
+impl<I> FusedIterator for Styled<I>
+where
+    I: FusedIterator,
+    I::Item: Stylable,
+{
+}
+
+/// Extends every iterator with [`styled`](StyledIterator::styled), for styling
+/// streamed text without collecting it first.
+pub trait StyledIterator: Iterator + Sized {
+    /// Paints each item with `style` as it is produced.
+    ///
+    /// Items can be anything [`Stylable`]: `&str`, `String` and other strings,
+    /// or rows such as `Vec<String>`, which are styled element by element.
+    /// To apply a formatting function such as [`bold`](super::bold) instead,
+    /// use [`Iterator::map`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminal_style::format::{Style, StyledIterator};
+    ///
+    /// let input = "alpha\nbeta";
+    /// let mut lines = input.lines().styled(Style::new().bold());
+    /// assert_eq!(lines.next(), Some("\x1b[1malpha\x1b[0m".to_string()));
+    /// assert_eq!(lines.next(), Some("\x1b[1mbeta\x1b[0m".to_string()));
+    /// assert_eq!(lines.next(), None);
+    ///
+    /// let rows = vec![vec!["a", "b"], vec!["c"]];
+    /// let styled: Vec<Vec<String>> = rows.into_iter().styled("italic".parse()?).collect();
+    /// assert_eq!(styled[1], vec!["\x1b[3mc\x1b[0m"]);
+    /// # Ok::<(), terminal_style::color::ColorConversionError>(())
+    /// ```
+    fn styled(self, style: Style) -> Styled<Self>
+    where
+        Self::Item: Stylable,
+    {
+        Styled { iter: self, style }
+    }
+}
+
+impl<I: Iterator> StyledIterator for I {}
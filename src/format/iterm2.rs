@@ -0,0 +1,113 @@
+//! Escape sequences for iTerm2 inline images.
+//!
+//! iTerm2, WezTerm and mintty show image files sent in an
+//! `OSC 1337 ; File=` sequence. Raw pixels are first encoded as PNG.
+
+use std::fmt::Write;
+
+use super::{base64, image::ImageData};
+use crate::color::ColorConversionError;
+
+/// Describes how [`encode`] sends an image: the number of cells it covers
+/// and whether it keeps its aspect ratio.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::image::ImageData;
+/// use terminal_style::format::iterm2::ITermImage;
+///
+/// let pixels = [0, 128, 255, 255];
+/// let image = ImageData::Rgba { width: 1, height: 1, pixels: &pixels };
+/// let sequence = ITermImage::new()
+///     .cells(40, 10)
+///     .preserve_aspect_ratio(false)
+///     .encode(&image)?;
+/// assert!(sequence.contains(";width=40;height=10;preserveAspectRatio=0:"));
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ITermImage {
+    cells: Option<(u32, u32)>,
+    preserve_aspect_ratio: bool,
+}
+
+impl Default for ITermImage {
+    fn default() -> Self {
+        ITermImage {
+            cells: None,
+            preserve_aspect_ratio: true,
+        }
+    }
+}
+
+impl ITermImage {
+    /// Creates the default options: shown at its natural size, keeping its
+    /// aspect ratio.
+    pub fn new() -> ITermImage {
+        ITermImage::default()
+    }
+
+    /// Scales the image to fit `cols` columns and `rows` rows of cells.
+    pub fn cells(mut self, cols: u32, rows: u32) -> ITermImage {
+        self.cells = Some((cols, rows));
+        self
+    }
+
+    /// Sets whether a scaled image keeps its aspect ratio, leaving part of
+    /// its cells empty, or is stretched to fill them.
+    pub fn preserve_aspect_ratio(mut self, preserve: bool) -> ITermImage {
+        self.preserve_aspect_ratio = preserve;
+        self
+    }
+
+    /// Encodes an image. See [`encode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColorConversionError::InvalidRgb`] if raw pixel data is not
+    /// the right length for the image's size.
+    pub fn encode(&self, image: &ImageData) -> Result<String, ColorConversionError> {
+        image.check()?;
+        let file = image.to_png();
+
+        let mut out = format!("\x1b]1337;File=inline=1;size={}", file.len());
+        if let Some((cols, rows)) = self.cells {
+            let _ = write!(out, ";width={};height={}", cols, rows);
+        }
+        let _ = write!(
+            out,
+            ";preserveAspectRatio={}:{}\x07",
+            u8::from(self.preserve_aspect_ratio),
+            base64::encode(&file)
+        );
+        Ok(out)
+    }
+}
+
+/// Encodes an image as an iTerm2 inline image sequence that displays it at
+/// the cursor, at its natural size.
+///
+/// Raw RGB and RGBA pixels are encoded as a PNG file first. Use
+/// [`ITermImage`] to give the image a size in cells.
+///
+/// # Errors
+///
+/// Returns [`ColorConversionError::InvalidRgb`] if raw pixel data is not
+/// the right length for the image's size.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::image::ImageData;
+/// use terminal_style::format::iterm2;
+///
+/// let pixels = [255, 0, 0];
+/// let sequence = iterm2::encode(&ImageData::Rgb { width: 1, height: 1, pixels: &pixels })?;
+/// assert!(sequence.starts_with("\x1b]1337;File=inline=1;size="));
+/// assert!(sequence.ends_with('\x07'));
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+pub fn encode(image: &ImageData) -> Result<String, ColorConversionError> {
+    ITermImage::new().encode(image)
+}
@@ -0,0 +1,188 @@
+//! Escape sequences for the kitty graphics protocol.
+//!
+//! Kitty, WezTerm, Ghostty and Konsole can show images sent as APC
+//! (`ESC _ G ... ESC \`) sequences. The image data is base64 encoded and
+//! split into chunks of at most 4096 bytes, as the protocol requires.
+
+use std::{fmt::Write, num::NonZeroU32};
+
+use super::{base64, image::ImageData};
+use crate::color::ColorConversionError;
+
+/// The largest amount of base64 data the protocol allows in one sequence.
+const CHUNK_SIZE: usize = 4096;
+
+/// Describes how [`encode`] sends an image: its id, the number of cells it
+/// covers and whether the terminal should reply.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::image::ImageData;
+/// use terminal_style::format::kitty::KittyImage;
+///
+/// let pixels = [255, 0, 0];
+/// let image = ImageData::Rgb { width: 1, height: 1, pixels: &pixels };
+/// let sequence = KittyImage::new().id(7).cells(2, 1).quiet(true).encode(&image)?;
+/// assert_eq!(sequence, "\x1b_Ga=T,f=24,s=1,v=1,i=7,c=2,r=1,q=2;/wAA\x1b\\");
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct KittyImage {
+    id: Option<NonZeroU32>,
+    cells: Option<(u32, u32)>,
+    quiet: bool,
+}
+
+impl KittyImage {
+    /// Creates the default options: no id, shown at its natural size, and
+    /// the terminal replies as usual.
+    pub fn new() -> KittyImage {
+        KittyImage::default()
+    }
+
+    /// Gives the image an id, so it can be deleted later with [`delete`].
+    ///
+    /// Ids must be greater than 0; an id of 0 leaves the image without one.
+    pub fn id(mut self, id: u32) -> KittyImage {
+        self.id = NonZeroU32::new(id);
+        self
+    }
+
+    /// Scales the image to cover `cols` columns and `rows` rows of cells.
+    pub fn cells(mut self, cols: u32, rows: u32) -> KittyImage {
+        self.cells = Some((cols, rows));
+        self
+    }
+
+    /// Asks the terminal not to reply, even with errors. Without it, the
+    /// terminal's replies arrive on standard input.
+    pub fn quiet(mut self, quiet: bool) -> KittyImage {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Encodes an image. See [`encode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColorConversionError::InvalidRgb`] if raw pixel data is not
+    /// the right length for the image's size.
+    pub fn encode(&self, image: &ImageData) -> Result<String, ColorConversionError> {
+        image.check()?;
+
+        // Transmit the image and display it at the cursor
+        let mut control = String::from("a=T");
+        let payload = match *image {
+            ImageData::Rgb {
+                width,
+                height,
+                pixels,
+            } => {
+                let _ = write!(control, ",f=24,s={},v={}", width, height);
+                pixels
+            }
+            ImageData::Rgba {
+                width,
+                height,
+                pixels,
+            } => {
+                let _ = write!(control, ",f=32,s={},v={}", width, height);
+                pixels
+            }
+            ImageData::Png(png) => {
+                control.push_str(",f=100");
+                png
+            }
+        };
+        if let Some(id) = self.id {
+            let _ = write!(control, ",i={}", id);
+        }
+        if let Some((cols, rows)) = self.cells {
+            let _ = write!(control, ",c={},r={}", cols, rows);
+        }
+        let quiet = if self.quiet { ",q=2" } else { "" };
+        control.push_str(quiet);
+
+        let data = base64::encode(payload);
+        let chunks: Vec<&str> = data
+            .as_bytes()
+            .chunks(CHUNK_SIZE)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+            .collect();
+        if chunks.len() <= 1 {
+            return Ok(format!("\x1b_G{};{}\x1b\\", control, data));
+        }
+
+        // Later chunks carry only whether more follow
+        let mut out = String::with_capacity(data.len() + chunks.len() * 16);
+        for (i, chunk) in chunks.iter().enumerate() {
+            let more = u8::from(i + 1 < chunks.len());
+            if i == 0 {
+                let _ = write!(out, "\x1b_G{},m={};{}\x1b\\", control, more, chunk);
+            } else {
+                let _ = write!(out, "\x1b_Gm={}{};{}\x1b\\", more, quiet, chunk);
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Encodes an image as kitty graphics protocol sequences that display it
+/// at the cursor, at its natural size.
+///
+/// Raw RGB and RGBA pixels are sent as they are; PNG files are decoded by
+/// the terminal. Use [`KittyImage`] to give the image an id or a size in cells.
+///
+/// # Errors
+///
+/// Returns [`ColorConversionError::InvalidRgb`] if raw pixel data is not
+/// the right length for the image's size.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::image::ImageData;
+/// use terminal_style::format::kitty;
+///
+/// let pixels = [0, 0, 255, 128];
+/// let image = ImageData::Rgba { width: 1, height: 1, pixels: &pixels };
+/// assert_eq!(kitty::encode(&image)?, "\x1b_Ga=T,f=32,s=1,v=1;AAD/gA==\x1b\\");
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+pub fn encode(image: &ImageData) -> Result<String, ColorConversionError> {
+    KittyImage::new().encode(image)
+}
+
+/// Deletes the image with the given id from the screen and frees its data.
+///
+/// An id of 0 means no id, as in [`KittyImage::id`], so it deletes nothing
+/// and returns an empty string.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::kitty;
+///
+/// assert_eq!(kitty::delete(7), "\x1b_Ga=d,d=I,i=7\x1b\\");
+/// assert_eq!(kitty::delete(0), "");
+/// ```
+pub fn delete(id: u32) -> String {
+    match NonZeroU32::new(id) {
+        Some(id) => format!("\x1b_Ga=d,d=I,i={}\x1b\\", id),
+        None => String::new(),
+    }
+}
+
+/// Deletes every image from the screen and frees their data.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::kitty;
+///
+/// assert_eq!(kitty::delete_all(), "\x1b_Ga=d,d=A\x1b\\");
+/// ```
+pub fn delete_all() -> String {
+    "\x1b_Ga=d,d=A\x1b\\".to_string()
+}
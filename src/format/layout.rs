@@ -0,0 +1,235 @@
+//! Layout of multi-line blocks.
+//!
+//! A block is anything that implements [`ToLines`]: a string, which is split
+//! at newlines, or a slice of lines, such as the `Vec<String>` produced by
+//! styling a vector. Lines are measured by their visible width, so blocks of
+//! styled text line up the same way plain text would.
+
+use super::width::{truncate, visible_width};
+
+/// Content that can be laid out as a block of lines, by [`join_horizontal`],
+/// [`join_vertical`], [`place`] and [`boxed`](fn@super::boxed).
+///
+/// Implemented for strings, which are split at newlines, and for slices,
+/// arrays and `Vec`s of strings, whose elements are each split the same way.
+pub trait ToLines {
+    /// Returns the lines of the content.
+    fn to_lines(&self) -> Vec<&str>;
+}
+
+impl ToLines for str {
+    fn to_lines(&self) -> Vec<&str> {
+        self.split('\n').collect()
+    }
+}
+
+impl ToLines for String {
+    fn to_lines(&self) -> Vec<&str> {
+        self.as_str().to_lines()
+    }
+}
+
+impl<S: AsRef<str>> ToLines for [S] {
+    fn to_lines(&self) -> Vec<&str> {
+        self.iter()
+            .flat_map(|line| line.as_ref().split('\n'))
+            .collect()
+    }
+}
+
+impl<S: AsRef<str>> ToLines for Vec<S> {
+    fn to_lines(&self) -> Vec<&str> {
+        self.as_slice().to_lines()
+    }
+}
+
+impl<S: AsRef<str>, const N: usize> ToLines for [S; N] {
+    fn to_lines(&self) -> Vec<&str> {
+        self.as_slice().to_lines()
+    }
+}
+
+impl<T: ToLines + ?Sized> ToLines for &T {
+    fn to_lines(&self) -> Vec<&str> {
+        (**self).to_lines()
+    }
+}
+
+/// Horizontal alignment of lines within a wider space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HAlign {
+    /// Content at the left, padding at the right.
+    #[default]
+    Left,
+    /// Padding split evenly, with any odd column on the right.
+    Center,
+    /// Padding at the left, content at the right.
+    Right,
+}
+
+/// Vertical alignment of blocks within a taller space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VAlign {
+    /// Content at the top, padding below.
+    #[default]
+    Top,
+    /// Padding split evenly, with any odd line below.
+    Middle,
+    /// Padding above, content at the bottom.
+    Bottom,
+}
+
+impl HAlign {
+    /// Splits `extra` columns of padding into the amounts before and after the content.
+    fn split(self, extra: usize) -> (usize, usize) {
+        match self {
+            HAlign::Left => (0, extra),
+            HAlign::Center => (extra / 2, extra - extra / 2),
+            HAlign::Right => (extra, 0),
+        }
+    }
+}
+
+impl VAlign {
+    /// Splits `extra` lines of padding into the amounts above and below the content.
+    fn split(self, extra: usize) -> (usize, usize) {
+        match self {
+            VAlign::Top => (0, extra),
+            VAlign::Middle => (extra / 2, extra - extra / 2),
+            VAlign::Bottom => (extra, 0),
+        }
+    }
+}
+
+/// Pads a line with spaces to `width` visible columns.
+///
+/// Lines that are already at least `width` wide are returned unchanged.
+pub(crate) fn pad(line: &str, width: usize, align: HAlign) -> String {
+    let (before, after) = align.split(width.saturating_sub(visible_width(line)));
+    format!("{}{}{}", " ".repeat(before), line, " ".repeat(after))
+}
+
+/// Returns the visible width of the widest line of a block.
+pub(crate) fn block_width<S: AsRef<str>>(block: &[S]) -> usize {
+    block
+        .iter()
+        .map(|line| visible_width(line.as_ref()))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Places blocks side by side.
+///
+/// Each block is padded to the width of its widest line, and blocks shorter
+/// than the tallest one are padded with blank lines according to `align`.
+///
+/// # Arguments
+///
+/// * `blocks` - The blocks to join, from left to right.
+/// * `align` - Where shorter blocks sit relative to the tallest one.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{bold, join_horizontal, VAlign};
+///
+/// let left = vec![bold("ab"), "c".to_string()];
+/// let right = vec!["| x".to_string()];
+/// let joined = join_horizontal(&[left, right], VAlign::Bottom);
+///
+/// assert_eq!(joined, vec!["\x1b[1mab\x1b[0m   ", "c | x"]);
+///
+/// // Strings are split at newlines
+/// assert_eq!(join_horizontal(&["a\nbc", "|"], VAlign::Top), vec!["a |", "bc "]);
+/// ```
+pub fn join_horizontal<B: ToLines>(blocks: &[B], align: VAlign) -> Vec<String> {
+    let blocks: Vec<Vec<&str>> = blocks.iter().map(ToLines::to_lines).collect();
+    let height = blocks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut lines = vec![String::new(); height];
+
+    for block in &blocks {
+        let width = block_width(block);
+        let (above, _) = align.split(height - block.len());
+
+        for (row, line) in lines.iter_mut().enumerate() {
+            match row.checked_sub(above).and_then(|i| block.get(i)) {
+                Some(content) => line.push_str(&pad(content, width, HAlign::Left)),
+                None => line.push_str(&" ".repeat(width)),
+            }
+        }
+    }
+    lines
+}
+
+/// Stacks blocks on top of each other.
+///
+/// Every line is padded to the width of the widest line across all blocks,
+/// according to `align`.
+///
+/// # Arguments
+///
+/// * `blocks` - The blocks to join, from top to bottom.
+/// * `align` - Where narrower lines sit relative to the widest one.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{join_vertical, HAlign};
+///
+/// let title = vec!["Title"];
+/// let body = vec!["a", "bcd"];
+/// let joined = join_vertical(&[title, body], HAlign::Center);
+///
+/// assert_eq!(joined, vec!["Title", "  a  ", " bcd "]);
+/// ```
+pub fn join_vertical<B: ToLines>(blocks: &[B], align: HAlign) -> Vec<String> {
+    let lines: Vec<&str> = blocks.iter().flat_map(ToLines::to_lines).collect();
+    let width = block_width(&lines);
+    lines.iter().map(|line| pad(line, width, align)).collect()
+}
+
+/// Places a block inside a box of exactly `width` columns by `height` lines.
+///
+/// The content is aligned within the box and the remaining space filled with
+/// spaces. Content that doesn't fit is cut off at the right and bottom.
+///
+/// # Arguments
+///
+/// * `block` - The lines to place.
+/// * `width` - The width of the box, in columns.
+/// * `height` - The height of the box, in lines.
+/// * `h_align` - Where the lines sit horizontally.
+/// * `v_align` - Where the block sits vertically.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{place, HAlign, VAlign};
+///
+/// let placed = place(&["hi"], 6, 3, HAlign::Right, VAlign::Middle);
+/// assert_eq!(placed, vec!["      ", "    hi", "      "]);
+///
+/// let cropped = place(&["long line", "second", "third"], 4, 2, HAlign::Left, VAlign::Top);
+/// assert_eq!(cropped, vec!["long", "seco"]);
+/// ```
+pub fn place<T: ToLines + ?Sized>(
+    block: &T,
+    width: usize,
+    height: usize,
+    h_align: HAlign,
+    v_align: VAlign,
+) -> Vec<String> {
+    let block = block.to_lines();
+    let content = &block[..block.len().min(height)];
+    let (above, below) = v_align.split(height - content.len());
+    let blank = " ".repeat(width);
+
+    let mut lines = vec![blank.clone(); above];
+    lines.extend(
+        content
+            .iter()
+            .map(|line| pad(&truncate(line, width), width, h_align)),
+    );
+    lines.resize(above + content.len() + below, blank);
+    lines
+}
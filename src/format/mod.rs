@@ -2,13 +2,58 @@
 //!
 //! This module provides functions for applying colors and text styles (like bold, italic, etc.)
 //! to various types of input (strings, vectors, 2D vectors) via the [`Stylable`] trait.
+//! Reusable combinations of colors and attributes are described by [`Style`].
 
+mod base64;
+pub mod border;
+pub mod boxed;
+pub mod canvas;
+pub mod chart;
 pub mod colors;
+pub mod highlight;
+pub mod image;
+pub mod indexed;
+pub mod iter;
+pub mod iterm2;
+pub mod kitty;
+pub mod layout;
+pub mod per_char;
+mod png;
+pub mod progress;
+pub mod range;
+pub mod render;
+mod sgr;
+pub mod sixel;
+pub mod spinner;
 pub mod stylable;
+pub mod style;
+pub mod table;
 pub mod text;
+pub mod tree;
+pub mod width;
+pub mod writer;
 
+pub use border::{Border, BorderChars};
+pub use boxed::{boxed, BoxStyle, Spacing};
+pub use canvas::{Canvas, CanvasMode};
+pub use chart::{bar_chart, sparkline, BarChart, Sparkline};
 pub use colors::{
-    background, background_ansi, background_rgb, color, color_ansi, color_rgb,
+    background, background_ansi, background_native, background_rgb, color, color_ansi,
+    color_native, color_rgb,
 };
+pub use highlight::{highlight, HighlightPattern};
+pub use indexed::{stripe_rows, style_column, style_row, CellIndex, IndexedItem, StylableIndexed};
+pub use iter::{Styled, StyledIterator};
+pub use layout::{join_horizontal, join_vertical, place, HAlign, ToLines, VAlign};
+pub use per_char::{hue_cycle, per_char, rainbow};
+pub use progress::{redraw, ProgressBar};
+pub use range::{style_range, style_ranges, TextRange};
+pub use render::render_grid;
+pub use spinner::{ColorCycle, Frames, Spinner, Ticker};
 pub use stylable::Stylable;
+pub use style::Style;
+pub use table::{table, Overflow, Table, TableBorder};
 pub use text::{bold, faint, inverse, italic, underline};
+pub use tree::{tree, Node, TreeGuides, TreeNode, TreeStyle};
+pub use width::visible_width;
+pub use writer::StyledWriter;
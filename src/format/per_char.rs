@@ -0,0 +1,91 @@
+use super::{sgr, stylable::Stylable, style::Style};
+use crate::color::{hsl_to_rgb, Color};
+
+/// Styles each visible character of the input individually.
+///
+/// The closure receives the index of each character within its string (escape
+/// sequences are not counted) and the character itself, and returns the style
+/// to add to it. Existing styling is kept, with the returned style layered on
+/// top (see [`Style::patch`]). Neighboring characters with the same style
+/// share a single escape sequence.
+///
+/// # Arguments
+///
+/// * `text` - The text to style.
+/// * `f` - A closure returning the style for the character at a given index.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{per_char, Style};
+///
+/// let alternating = per_char("abcd", |i, _| {
+///     if i % 2 == 0 { Style::new().bold() } else { Style::new() }
+/// });
+/// assert_eq!(alternating, "\x1b[1ma\x1b[0mb\x1b[1mc\x1b[0md");
+///
+/// let vowels = per_char("pear", |_, c| {
+///     if "aeiou".contains(c) { Style::new().underline() } else { Style::new() }
+/// });
+/// assert_eq!(vowels, "p\x1b[4mea\x1b[0mr");
+/// ```
+pub fn per_char<T, F>(text: T, f: F) -> T::Output
+where
+    T: Stylable,
+    F: Fn(usize, char) -> Style,
+{
+    text.apply(|s| sgr::restyle(s, |index, c, current| current.patch(&f(index, c))))
+}
+
+/// Colors the input with a rainbow, spreading the full hue circle across the
+/// visible characters of each string.
+///
+/// Uses 24-bit TrueColor.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::rainbow;
+///
+/// let s = rainbow("abc");
+/// assert_eq!(
+///     s,
+///     "\x1b[38;2;255;0;0ma\x1b[38;2;0;255;0mb\x1b[38;2;0;0;255mc\x1b[0m"
+/// );
+/// ```
+pub fn rainbow<T: Stylable>(text: T) -> T::Output {
+    text.apply(|s| {
+        let step = 360.0 / sgr::strip(s).chars().count().max(1) as f64;
+        hue_cycle_str(s, 0.0, step)
+    })
+}
+
+/// Colors the input by stepping around the hue circle, one step per visible character.
+///
+/// Uses fully saturated, 24-bit TrueColor hues. Each string starts again at `start_hue`.
+///
+/// # Arguments
+///
+/// * `text` - The text to style.
+/// * `start_hue` - The hue of the first character, in degrees.
+/// * `step` - The number of degrees to move for each following character.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::hue_cycle;
+///
+/// let s = hue_cycle("ab", 120.0, 120.0);
+/// assert_eq!(s, "\x1b[38;2;0;255;0ma\x1b[38;2;0;0;255mb\x1b[0m");
+/// ```
+pub fn hue_cycle<T: Stylable>(text: T, start_hue: f64, step: f64) -> T::Output {
+    text.apply(|s| hue_cycle_str(s, start_hue, step))
+}
+
+/// Colors a single string, starting at `start_hue` and moving `step` degrees per character.
+fn hue_cycle_str(s: &str, start_hue: f64, step: f64) -> String {
+    sgr::restyle(s, |index, _, current| {
+        let rgb = hsl_to_rgb(start_hue + index as f64 * step, 1.0, 0.5);
+        current.patch(&Style::new().fg(Color::Rgb(rgb)))
+    })
+}
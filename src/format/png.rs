@@ -0,0 +1,107 @@
+//! A minimal PNG encoder for handing raw pixels to terminals that only
+//! accept image files.
+//!
+//! The image data is stored without compression (zlib "stored" blocks), which
+//! keeps the encoder tiny. Terminal images are small and travel over a local
+//! pipe, so the larger size doesn't matter much.
+
+use std::sync::OnceLock;
+
+/// The eight bytes every PNG file starts with.
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// The largest amount of data a stored deflate block can hold.
+const MAX_STORED_BLOCK: usize = 0xFFFF;
+
+/// Encodes 8-bit RGB (`channels` = 3) or RGBA (`channels` = 4) pixels as a PNG file.
+///
+/// `pixels` must hold `width * height * channels` bytes, row by row.
+pub(crate) fn encode(width: u32, height: u32, channels: usize, pixels: &[u8]) -> Vec<u8> {
+    let color_type = if channels == 4 { 6 } else { 2 };
+    let mut header = Vec::with_capacity(13);
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // Bit depth, color type, compression, filter and interlace methods
+    header.extend([8, color_type, 0, 0, 0]);
+
+    // Each row is preceded by its filter type, 0 for none
+    let row_length = width as usize * channels;
+    let mut raw = Vec::with_capacity((row_length + 1) * height as usize);
+    if row_length > 0 {
+        for row in pixels.chunks(row_length) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+    } else {
+        raw.resize(height as usize, 0);
+    }
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Appends a chunk: its length, type, data and CRC.
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wraps data in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.len().div_ceil(MAX_STORED_BLOCK).max(1);
+    let mut out = Vec::with_capacity(data.len() + blocks * 5 + 6);
+    // Deflate with a 32K window, no preset dictionary, fastest level
+    out.extend([0x78, 0x01]);
+
+    let mut chunks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if chunks.peek().is_none() {
+        out.extend([1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(chunk) = chunks.next() {
+        let last = chunks.peek().is_none();
+        let length = chunk.len() as u16;
+        out.push(u8::from(last));
+        out.extend(length.to_le_bytes());
+        out.extend((!length).to_le_bytes());
+        out.extend_from_slice(chunk);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+/// The CRC-32 used by PNG chunks.
+fn crc32(data: &[u8]) -> u32 {
+    static TABLE: OnceLock<[u32; 256]> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        std::array::from_fn(|n| {
+            (0..8).fold(n as u32, |c, _| {
+                if c & 1 == 1 {
+                    0xEDB8_8320 ^ (c >> 1)
+                } else {
+                    c >> 1
+                }
+            })
+        })
+    });
+    !data.iter().fold(!0u32, |crc, &byte| {
+        table[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// The Adler-32 checksum that ends a zlib stream.
+fn adler32(data: &[u8]) -> u32 {
+    const MODULUS: u32 = 65521;
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % MODULUS;
+        (a, (b + a) % MODULUS)
+    });
+    (b << 16) | a
+}
@@ -0,0 +1,219 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use super::{
+    sgr::{self, Piece},
+    style::Style,
+};
+use crate::color::{ColorDepth, Gradient};
+
+/// The partial block characters, from one eighth to seven eighths of a cell.
+pub(crate) const EIGHTHS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// The character of a completely filled cell.
+pub(crate) const FULL: char = '█';
+
+/// Describes how to draw a progress bar: its width, fill, track and the
+/// fields shown around it.
+///
+/// The bar is drawn with block characters and has a precision of an eighth
+/// of a cell, so even narrow bars move smoothly.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::{ColorDepth, Gradient};
+/// use terminal_style::format::{ProgressBar, Style};
+///
+/// let bar = ProgressBar::new(10).label("Copying").percentage(true);
+/// assert_eq!(bar.render(0.45), "Copying ████▌       45%");
+///
+/// let fancy = ProgressBar::new(30)
+///     .fill(Gradient::new(["#FF0000", "#FFFF00", "#00FF00"])?)
+///     .color_depth(ColorDepth::Ansi256)
+///     .track('─', Style::new().faint());
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressBar {
+    width: usize,
+    fill: Option<Gradient>,
+    depth: ColorDepth,
+    track: (char, Style),
+    label: Option<String>,
+    percentage: bool,
+}
+
+impl ProgressBar {
+    /// Creates an unstyled progress bar `width` columns wide, with a blank
+    /// track and no label or percentage.
+    pub fn new(width: usize) -> ProgressBar {
+        ProgressBar {
+            width,
+            fill: None,
+            depth: ColorDepth::TrueColor,
+            track: (' ', Style::new()),
+            label: None,
+            percentage: false,
+        }
+    }
+
+    /// Colors the filled part of the bar: a solid `[r, g, b]` color, or a
+    /// [`Gradient`] stretched across the whole width of the bar.
+    pub fn fill(mut self, fill: impl Into<Gradient>) -> ProgressBar {
+        self.fill = Some(fill.into());
+        self
+    }
+
+    /// Sets whether the fill is drawn in TrueColor or with the 256-color palette.
+    ///
+    /// Defaults to [`ColorDepth::TrueColor`].
+    pub fn color_depth(mut self, depth: ColorDepth) -> ProgressBar {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the character and style of the empty part of the bar.
+    ///
+    /// The style's background also shows behind the partly filled cell.
+    pub fn track(mut self, c: char, style: Style) -> ProgressBar {
+        self.track = (c, style);
+        self
+    }
+
+    /// Shows a label before the bar. The label may already be styled.
+    pub fn label(mut self, label: impl Into<String>) -> ProgressBar {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets whether the percentage done is shown after the bar.
+    pub fn percentage(mut self, show: bool) -> ProgressBar {
+        self.percentage = show;
+        self
+    }
+
+    /// Renders the bar for `fraction` done, from `0.0` to `1.0`.
+    ///
+    /// Values outside that range are clamped.
+    pub fn render(&self, fraction: f64) -> String {
+        self.render_fields(fraction, None)
+    }
+
+    /// Renders the bar for `fraction` done, followed by the estimated time
+    /// remaining given that `elapsed` has passed so far.
+    ///
+    /// The estimate assumes progress continues at its average rate so far,
+    /// and shows `--:--` until there is any progress.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use terminal_style::format::ProgressBar;
+    ///
+    /// let bar = ProgressBar::new(4).percentage(true);
+    /// assert_eq!(
+    ///     bar.render_with_eta(0.25, Duration::from_secs(30)),
+    ///     "█     25% ETA 1:30"
+    /// );
+    /// ```
+    pub fn render_with_eta(&self, fraction: f64, elapsed: Duration) -> String {
+        self.render_fields(fraction, Some(elapsed))
+    }
+
+    fn render_fields(&self, fraction: f64, elapsed: Option<Duration>) -> String {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+
+        let mut out = String::new();
+        if let Some(label) = &self.label {
+            out.push_str(label);
+            out.push(' ');
+        }
+        out.push_str(&self.bar(fraction));
+        if self.percentage {
+            // Only show 100% once the work is really done
+            let percent = if fraction < 1.0 {
+                (fraction * 100.0).round().min(99.0) as u32
+            } else {
+                100
+            };
+            out.push_str(&format!(" {:>3}%", percent));
+        }
+        if let Some(elapsed) = elapsed {
+            out.push_str(" ETA ");
+            out.push_str(&eta(fraction, elapsed));
+        }
+        out
+    }
+
+    /// Draws the bar itself, merging the escapes of neighboring cells that
+    /// share a color.
+    fn bar(&self, fraction: f64) -> String {
+        let eighths = (fraction * self.width as f64 * 8.0).round() as usize;
+        let (full, partial) = (eighths / 8, eighths % 8);
+        let (track_char, track_style) = self.track;
+
+        let fill_style = |cell: usize| match &self.fill {
+            Some(gradient) => {
+                let t = (cell as f64 + 0.5) / self.width as f64;
+                Style::new().fg(self.depth.color(gradient.at(t)))
+            }
+            None => Style::new(),
+        };
+        let cells = (0..self.width).map(|cell| {
+            if cell < full {
+                Piece::Char(FULL, fill_style(cell))
+            } else if cell == full && partial > 0 {
+                Piece::Char(EIGHTHS[partial - 1], track_style.patch(&fill_style(cell)))
+            } else {
+                Piece::Char(track_char, track_style)
+            }
+        });
+        sgr::render(cells, Style::new())
+    }
+}
+
+/// Formats the time left as `m:ss`, or `h:mm:ss` from an hour up.
+fn eta(fraction: f64, elapsed: Duration) -> String {
+    if fraction <= 0.0 {
+        return "--:--".to_string();
+    }
+    let remaining = (elapsed.as_secs_f64() * (1.0 - fraction) / fraction).round() as u64;
+    let (hours, minutes, seconds) = (remaining / 3600, remaining / 60 % 60, remaining % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Redraws a line in place: returns to the start of the line, writes `line`,
+/// clears anything left over from a longer previous line, and flushes.
+///
+/// Call it repeatedly with the output of [`ProgressBar::render`] to animate a
+/// bar, then write a newline when done.
+///
+/// # Errors
+///
+/// Returns any error from writing to or flushing `out`.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{redraw, ProgressBar};
+///
+/// let bar = ProgressBar::new(4);
+/// let mut out = Vec::new();
+/// redraw(&mut out, &bar.render(0.5))?;
+/// assert_eq!(out, "\r██  \x1b[K".as_bytes());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn redraw<W: Write + ?Sized>(out: &mut W, line: &str) -> io::Result<()> {
+    write!(out, "\r{}\x1b[K", line)?;
+    out.flush()
+}
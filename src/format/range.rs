@@ -0,0 +1,215 @@
+use std::ops::{
+    Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
+
+use super::{sgr, stylable::Stylable, style::Style, width::char_width};
+
+/// The unit a [`TextRange`] is measured in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Chars,
+    Columns,
+    Bytes,
+}
+
+/// A range of the visible text of a string, for [`style_range`] and [`style_ranges`].
+///
+/// Positions never count escape sequences, so a range refers to the same text
+/// whether or not the string has already been styled. Plain Rust ranges
+/// (`2..5`, `..3`, `4..=6`, ...) convert into character ranges.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::TextRange;
+///
+/// let by_char: TextRange = (2..5).into();
+/// assert_eq!(by_char, TextRange::chars(2..5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextRange {
+    unit: Unit,
+    start: usize,
+    end: usize,
+}
+
+impl TextRange {
+    /// A range of characters (`char`s), counted from 0.
+    pub fn chars<R: RangeBounds<usize>>(range: R) -> Self {
+        Self::new(Unit::Chars, range)
+    }
+
+    /// A range of terminal columns, counted from 0.
+    ///
+    /// Wide characters span two columns and are styled if either of them is in
+    /// the range. Combining marks go along with the character they modify.
+    pub fn columns<R: RangeBounds<usize>>(range: R) -> Self {
+        Self::new(Unit::Columns, range)
+    }
+
+    /// A range of byte offsets into the visible text, as produced by lexers
+    /// and parsers working on the unstyled source.
+    ///
+    /// A character is styled if its first byte is in the range.
+    pub fn bytes<R: RangeBounds<usize>>(range: R) -> Self {
+        Self::new(Unit::Bytes, range)
+    }
+
+    fn new<R: RangeBounds<usize>>(unit: Unit, range: R) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.saturating_add(1),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => usize::MAX,
+        };
+        TextRange { unit, start, end }
+    }
+
+    /// Returns `true` if the character at `position` falls in the range.
+    fn contains(&self, position: &Position) -> bool {
+        match self.unit {
+            Unit::Chars => (self.start..self.end).contains(&position.index),
+            Unit::Bytes => (self.start..self.end).contains(&position.byte),
+            Unit::Columns => position.columns.start < self.end && self.start < position.columns.end,
+        }
+    }
+}
+
+/// Implements the conversion from std ranges to character ranges.
+macro_rules! impl_from_range {
+    ($($range:ty),*) => {
+        $(
+            impl From<$range> for TextRange {
+                fn from(range: $range) -> Self {
+                    TextRange::chars(range)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_range!(
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeTo<usize>,
+    RangeToInclusive<usize>
+);
+
+/// Where a visible character sits, in each of the units a range can use.
+struct Position {
+    index: usize,
+    byte: usize,
+    columns: Range<usize>,
+}
+
+/// Computes the position of every character of `visible`.
+fn positions(visible: &str) -> Vec<Position> {
+    let mut positions: Vec<Position> = Vec::with_capacity(visible.len());
+    let mut column = 0;
+    for (index, (byte, c)) in visible.char_indices().enumerate() {
+        let width = char_width(c);
+        let columns = match positions.last() {
+            // Zero-width characters belong to the one before them
+            Some(previous) if width == 0 => previous.columns.clone(),
+            _ => column..column + width.max(1),
+        };
+        column += width;
+        positions.push(Position {
+            index,
+            byte,
+            columns,
+        });
+    }
+    positions
+}
+
+/// Applies `style` to part of a string's visible text.
+///
+/// Any styling already in the string is kept, with `style` layered on top (see
+/// [`Style::patch`]). Parts of the range past the end of the text are ignored.
+///
+/// # Arguments
+///
+/// * `text` - The text to style.
+/// * `range` - A [`TextRange`], or a plain range of characters.
+/// * `style` - The style to apply.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{style_range, Style, TextRange};
+///
+/// let keyword = style_range("let x = 1;", 0..3, Style::new().bold());
+/// assert_eq!(keyword, "\x1b[1mlet\x1b[0m x = 1;");
+///
+/// let wide = style_range("日本語", TextRange::columns(2..4), Style::new().underline());
+/// assert_eq!(wide, "日\x1b[4m本\x1b[0m語");
+/// ```
+pub fn style_range<T, R>(text: T, range: R, style: Style) -> T::Output
+where
+    T: Stylable,
+    R: Into<TextRange>,
+{
+    let ranges = [(range.into(), style)];
+    text.apply(|s| style_ranges_str(s, &ranges))
+}
+
+/// Applies several styles to parts of a string's visible text.
+///
+/// Where ranges overlap, later entries take priority: their colors replace
+/// those of earlier entries and their attributes are added, the same way
+/// [`Style::patch`] layers styles.
+///
+/// # Arguments
+///
+/// * `text` - The text to style.
+/// * `ranges` - Pairs of ranges and the styles to apply to them, lowest priority first.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Color;
+/// use terminal_style::format::{style_ranges, Style, TextRange};
+///
+/// // Token spans from a lexer, as byte offsets into the source
+/// let source = "fn main() {}";
+/// let tokens = [
+///     (TextRange::bytes(0..2), Style::new().fg(Color::Ansi16(5))),
+///     (TextRange::bytes(3..7), Style::new().fg(Color::Ansi16(4))),
+/// ];
+/// let highlighted = style_ranges(source, &tokens);
+/// assert_eq!(highlighted, "\x1b[35mfn\x1b[0m \x1b[34mmain\x1b[0m() {}");
+/// ```
+pub fn style_ranges<T, R>(text: T, ranges: &[(R, Style)]) -> T::Output
+where
+    T: Stylable,
+    R: Clone + Into<TextRange>,
+{
+    let ranges: Vec<(TextRange, Style)> = ranges
+        .iter()
+        .map(|(range, style)| (range.clone().into(), *style))
+        .collect();
+    text.apply(|s| style_ranges_str(s, &ranges))
+}
+
+/// Styles the ranges of a single string.
+fn style_ranges_str(s: &str, ranges: &[(TextRange, Style)]) -> String {
+    if ranges.is_empty() {
+        return s.to_string();
+    }
+
+    let positions = positions(&sgr::strip(s));
+    sgr::restyle(s, |index, _, current| match positions.get(index) {
+        Some(position) => ranges
+            .iter()
+            .filter(|(range, _)| range.contains(position))
+            .fold(current, |style, (_, layer)| style.patch(layer)),
+        None => current,
+    })
+}
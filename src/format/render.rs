@@ -0,0 +1,44 @@
+use super::{sgr, style::Style};
+
+/// Joins a grid of styled cells into a single string, one line per row.
+///
+/// Printing styled cells one by one repeats a full set and reset sequence for
+/// every cell. `render_grid` produces the same picture but only emits the
+/// attributes that change between neighboring cells, so runs of cells that
+/// share a color cost a single escape sequence. Each row ends with a reset,
+/// so background colors never bleed into the next line.
+///
+/// Rows are separated by `\n`, with no newline after the last row.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{background_rgb, render_grid};
+///
+/// let black = [0, 0, 0];
+/// let white = [255, 255, 255];
+/// let grid = vec![
+///     vec![background_rgb(black, " ")?, background_rgb(black, " ")?],
+///     vec![background_rgb(black, " ")?, background_rgb(white, " ")?],
+/// ];
+///
+/// assert_eq!(
+///     render_grid(&grid),
+///     "\x1b[48;2;0;0;0m  \x1b[0m\n\x1b[48;2;0;0;0m \x1b[48;2;255;255;255m \x1b[0m"
+/// );
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+pub fn render_grid<R, S>(grid: &[R]) -> String
+where
+    R: AsRef<[S]>,
+    S: AsRef<str>,
+{
+    grid.iter()
+        .map(|row| {
+            let line: String = row.as_ref().iter().map(|cell| cell.as_ref()).collect();
+            let parsed = sgr::parse(&line);
+            sgr::render(parsed.pieces, Style::new())
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
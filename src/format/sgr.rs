@@ -0,0 +1,389 @@
+//! Parsing and rendering of already-styled strings.
+//!
+//! Styled text is broken into [`Piece`]s: visible characters, each carrying
+//! the [`Style`] active at that point, and non-SGR escape sequences (cursor
+//! movement, hyperlinks, ...) that are passed through untouched. After the
+//! styles of individual characters have been changed, [`render`] joins the
+//! pieces back together, emitting only the SGR transitions that are needed.
+
+use super::style::Style;
+use crate::color::Color;
+
+/// A piece of a parsed styled string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Piece<'a> {
+    /// A visible character and the style it is displayed with.
+    Char(char, Style),
+    /// A non-SGR escape sequence, kept verbatim.
+    Escape(&'a str),
+}
+
+/// A styled string broken into pieces.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Parsed<'a> {
+    pub(crate) pieces: Vec<Piece<'a>>,
+    /// The style left active after the last piece.
+    pub(crate) end: Style,
+}
+
+/// A lexical token of a string containing escape sequences.
+enum Token<'a> {
+    Text(&'a str),
+    /// The parameters of an SGR (`ESC [ ... m`) sequence.
+    Sgr(&'a str),
+    /// Any other escape sequence, including its introducer.
+    Escape(&'a str),
+}
+
+/// Splits a string into text, SGR sequences and other escape sequences.
+fn tokenize(s: &str) -> Vec<Token<'_>> {
+    let bytes = s.as_bytes();
+    let mut tokens = Vec::new();
+    let mut text_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != 0x1b {
+            i += 1;
+            continue;
+        }
+        if text_start < i {
+            tokens.push(Token::Text(&s[text_start..i]));
+        }
+
+        let start = i;
+        let end = escape_end(bytes, i);
+        let sequence = &s[start..end];
+        match sequence
+            .strip_prefix("\x1b[")
+            .and_then(|rest| rest.strip_suffix('m'))
+        {
+            Some(params) if params.bytes().all(|b| (0x30..=0x3f).contains(&b)) => {
+                tokens.push(Token::Sgr(params))
+            }
+            _ => tokens.push(Token::Escape(sequence)),
+        }
+
+        i = end;
+        text_start = end;
+    }
+
+    if text_start < bytes.len() {
+        tokens.push(Token::Text(&s[text_start..]));
+    }
+    tokens
+}
+
+/// Returns the end (exclusive) of the escape sequence starting at `start`.
+fn escape_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    match bytes.get(i) {
+        // CSI: parameter and intermediate bytes, then a final byte
+        Some(b'[') => {
+            i += 1;
+            while i < bytes.len() && (0x20..=0x3f).contains(&bytes[i]) {
+                i += 1;
+            }
+            // A sequence cut short by anything else ends before it
+            match bytes.get(i) {
+                Some(0x40..=0x7e) => i + 1,
+                _ => i,
+            }
+        }
+        // OSC, DCS, SOS, PM and APC: terminated by BEL (OSC only) or ST (`ESC \`)
+        Some(b']' | b'P' | b'X' | b'^' | b'_') => {
+            let osc = bytes[i] == b']';
+            i += 1;
+            while i < bytes.len() {
+                if osc && bytes[i] == 0x07 {
+                    return i + 1;
+                }
+                if bytes[i] == 0x1b && bytes.get(i + 1) == Some(&b'\\') {
+                    return i + 2;
+                }
+                i += 1;
+            }
+            bytes.len()
+        }
+        // Two-character escape, keeping multi-byte characters whole
+        Some(_) => {
+            i += 1;
+            while i < bytes.len() && (bytes[i] & 0xc0) == 0x80 {
+                i += 1;
+            }
+            i
+        }
+        None => i,
+    }
+}
+
+/// Parses a styled string, starting from a plain style.
+pub(crate) fn parse(s: &str) -> Parsed<'_> {
+    let mut style = Style::new();
+    let mut pieces = Vec::with_capacity(s.len());
+
+    for token in tokenize(s) {
+        match token {
+            Token::Text(text) => pieces.extend(text.chars().map(|c| Piece::Char(c, style))),
+            Token::Sgr(params) => style.apply_sgr(params),
+            Token::Escape(sequence) => pieces.push(Piece::Escape(sequence)),
+        }
+    }
+
+    Parsed { pieces, end: style }
+}
+
+/// Joins pieces into a string, starting from a plain style and finishing in `end`.
+///
+/// Only the attributes that differ between neighboring characters are emitted.
+pub(crate) fn render<'a, I>(pieces: I, end: Style) -> String
+where
+    I: IntoIterator<Item = Piece<'a>>,
+{
+    let mut out = String::new();
+    let mut current = Style::new();
+
+    for piece in pieces {
+        match piece {
+            Piece::Char(c, style) => {
+                out.push_str(&current.transition(&style));
+                current = style;
+                out.push(c);
+            }
+            Piece::Escape(sequence) => out.push_str(sequence),
+        }
+    }
+
+    out.push_str(&current.transition(&end));
+    out
+}
+
+/// Applies a styling function to the visible characters of a styled string.
+///
+/// The closure receives the index of each visible character (escape sequences
+/// are not counted), the character, and its current style, and returns the
+/// style to display it with.
+pub(crate) fn restyle<F>(s: &str, mut f: F) -> String
+where
+    F: FnMut(usize, char, Style) -> Style,
+{
+    let parsed = parse(s);
+    let mut index = 0;
+    let pieces = parsed.pieces.into_iter().map(|piece| match piece {
+        Piece::Char(c, style) => {
+            let piece = Piece::Char(c, f(index, c, style));
+            index += 1;
+            piece
+        }
+        escape => escape,
+    });
+    render(pieces, parsed.end)
+}
+
+/// Removes all escape sequences, keeping only the visible characters.
+pub(crate) fn strip(s: &str) -> String {
+    tokenize(s)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(text),
+            _ => None,
+        })
+        .collect()
+}
+
+impl Style {
+    /// Returns this style with `other` layered on top.
+    ///
+    /// Colors set in `other` replace those in `self`; attributes are combined.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminal_style::color::Color;
+    /// use terminal_style::format::Style;
+    ///
+    /// let base = Style::new().italic().fg(Color::Ansi16(1));
+    /// let highlight = Style::new().bold().fg(Color::Ansi16(3));
+    /// assert_eq!(base.patch(&highlight), Style::new().bold().italic().fg(Color::Ansi16(3)));
+    /// ```
+    pub fn patch(&self, other: &Style) -> Style {
+        Style {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            bold: self.bold || other.bold,
+            faint: self.faint || other.faint,
+            italic: self.italic || other.italic,
+            underline: self.underline || other.underline,
+            blink: self.blink || other.blink,
+            inverse: self.inverse || other.inverse,
+            hidden: self.hidden || other.hidden,
+            strikethrough: self.strikethrough || other.strikethrough,
+        }
+    }
+
+    /// Returns the shortest escape sequence that changes the terminal from
+    /// this style to `to`, or an empty string if they are the same.
+    ///
+    /// Returning to a plain style always uses a full reset (`\x1b[0m`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminal_style::color::Color;
+    /// use terminal_style::format::Style;
+    ///
+    /// let red = Style::new().fg(Color::Ansi16(1));
+    /// let bold_red = red.bold();
+    /// assert_eq!(red.transition(&bold_red), "\x1b[1m");
+    /// assert_eq!(bold_red.transition(&red), "\x1b[22m");
+    /// assert_eq!(red.transition(&Style::new()), "\x1b[0m");
+    /// ```
+    pub fn transition(&self, to: &Style) -> String {
+        if self == to {
+            return String::new();
+        }
+        if to.is_plain() {
+            return "\x1b[0m".to_string();
+        }
+
+        let mut diff: Vec<String> = Vec::new();
+        let mut from = *self;
+
+        // Bold and faint share a single "normal intensity" code.
+        if (from.bold && !to.bold) || (from.faint && !to.faint) {
+            diff.push("22".to_string());
+            from.bold = false;
+            from.faint = false;
+        }
+
+        let toggles = [
+            (from.bold, to.bold, "1", ""),
+            (from.faint, to.faint, "2", ""),
+            (from.italic, to.italic, "3", "23"),
+            (from.underline, to.underline, "4", "24"),
+            (from.blink, to.blink, "5", "25"),
+            (from.inverse, to.inverse, "7", "27"),
+            (from.hidden, to.hidden, "8", "28"),
+            (from.strikethrough, to.strikethrough, "9", "29"),
+        ];
+        for (was, is, on, off) in toggles {
+            if was != is {
+                diff.push(if is { on } else { off }.to_string());
+            }
+        }
+
+        if from.fg != to.fg {
+            diff.push(to.fg.unwrap_or(Color::Default).fg_params());
+        }
+        if from.bg != to.bg {
+            diff.push(to.bg.unwrap_or(Color::Default).bg_params());
+        }
+
+        let diff = diff.join(";");
+        let reset = format!("0;{}", to.params());
+        format!(
+            "\x1b[{}m",
+            if reset.len() < diff.len() {
+                reset
+            } else {
+                diff
+            }
+        )
+    }
+
+    /// Updates the style with the parameters of an SGR sequence, as a terminal would.
+    ///
+    /// Unsupported parameters are ignored.
+    pub(crate) fn apply_sgr(&mut self, params: &str) {
+        // An empty parameter means 0; anything unparsable is skipped.
+        let code = |param: &str| -> Option<u16> {
+            if param.is_empty() {
+                Some(0)
+            } else {
+                param.parse().ok()
+            }
+        };
+        let params: Vec<&str> = params.split(';').collect();
+
+        let mut i = 0;
+        while i < params.len() {
+            // Colon separated sub-parameters, e.g. `38:2::255:0:0`
+            if params[i].contains(':') {
+                let sub: Vec<u16> = params[i].split(':').map(|p| code(p).unwrap_or(0)).collect();
+                let color = match sub.as_slice() {
+                    [_, 5, index, ..] => Some(Color::Ansi256((*index).min(255) as u8)),
+                    [_, 2, _, r, g, b, ..] | [_, 2, r, g, b] => {
+                        Some(Color::Rgb([*r, *g, *b].map(|v| v.min(255) as u8)))
+                    }
+                    _ => None,
+                };
+                match sub.first() {
+                    Some(38) => self.fg = color.or(self.fg),
+                    Some(48) => self.bg = color.or(self.bg),
+                    _ => {}
+                }
+                i += 1;
+                continue;
+            }
+
+            match code(params[i]) {
+                Some(0) => *self = Style::new(),
+                Some(1) => self.bold = true,
+                Some(2) => self.faint = true,
+                Some(3) => self.italic = true,
+                Some(4) => self.underline = true,
+                Some(5 | 6) => self.blink = true,
+                Some(7) => self.inverse = true,
+                Some(8) => self.hidden = true,
+                Some(9) => self.strikethrough = true,
+                Some(22) => {
+                    self.bold = false;
+                    self.faint = false;
+                }
+                Some(23) => self.italic = false,
+                Some(24) => self.underline = false,
+                Some(25) => self.blink = false,
+                Some(27) => self.inverse = false,
+                Some(28) => self.hidden = false,
+                Some(29) => self.strikethrough = false,
+                Some(n @ 30..=37) => self.fg = Some(Color::Ansi16(n as u8 - 30)),
+                Some(n @ 90..=97) => self.fg = Some(Color::Ansi16(n as u8 - 90 + 8)),
+                Some(n @ 40..=47) => self.bg = Some(Color::Ansi16(n as u8 - 40)),
+                Some(n @ 100..=107) => self.bg = Some(Color::Ansi16(n as u8 - 100 + 8)),
+                Some(39) => self.fg = None,
+                Some(49) => self.bg = None,
+                Some(n @ (38 | 48)) => {
+                    let rest: Vec<u16> = params[i + 1..]
+                        .iter()
+                        .map(|p| code(p).unwrap_or(0))
+                        .collect();
+                    let (color, used) = extended_color(&rest);
+                    if let Some(color) = color {
+                        if n == 38 {
+                            self.fg = Some(color);
+                        } else {
+                            self.bg = Some(color);
+                        }
+                    }
+                    i += used;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Reads the color following a `38` or `48` code, returning it along with
+/// the number of codes it took up.
+fn extended_color(codes: &[u16]) -> (Option<Color>, usize) {
+    let component = |i: usize| codes.get(i).map(|&v| v.min(255) as u8);
+    match codes.first() {
+        Some(5) => (component(1).map(Color::Ansi256), 2),
+        Some(2) => match (component(1), component(2), component(3)) {
+            (Some(r), Some(g), Some(b)) => (Some(Color::Rgb([r, g, b])), 4),
+            _ => (None, codes.len()),
+        },
+        _ => (None, codes.len()),
+    }
+}
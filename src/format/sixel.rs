@@ -0,0 +1,248 @@
+//! Encoding of raw RGB images as sixel graphics.
+//!
+//! Sixel is the bitmap format of DEC terminals, supported by foot, mlterm,
+//! WezTerm, xterm (when started with `-ti vt340`) and others. An image is sent
+//! as a single escape sequence. Its pixels are drawn in bands six rows high,
+//! one color register at a time.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::color::{dither, dither::check_buffer, ColorConversionError, Dither, Palette};
+
+/// Describes how [`encode`] reduces an image to at most 256 color registers.
+///
+/// Images with 256 colors or fewer keep their exact colors. Others are
+/// mapped to a [`Palette`], by default the xterm 256-color palette with
+/// Floyd–Steinberg dithering.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::{Dither, Palette};
+/// use terminal_style::format::sixel::SixelOptions;
+///
+/// let options = SixelOptions::new()
+///     .palette(Palette::ansi16())
+///     .dither(Dither::Bayer)
+///     .transparent([0, 0, 0]);
+/// let sixel = options.encode(1, 1, &[0, 0, 0])?;
+/// assert_eq!(sixel, "\x1bP0;1;0q\"1;1;1;1\x1b\\");
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SixelOptions {
+    palette: Option<Palette>,
+    dither: Dither,
+    transparent: Option<[u8; 3]>,
+}
+
+impl Default for SixelOptions {
+    fn default() -> Self {
+        SixelOptions {
+            palette: None,
+            dither: Dither::FloydSteinberg,
+            transparent: None,
+        }
+    }
+}
+
+impl SixelOptions {
+    /// Creates the default options: exact colors where possible, otherwise
+    /// the xterm palette with Floyd–Steinberg dithering, and no transparency.
+    pub fn new() -> SixelOptions {
+        SixelOptions::default()
+    }
+
+    /// Sets the palette that images with more than 256 colors are reduced to.
+    pub fn palette(mut self, palette: Palette) -> SixelOptions {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Sets how colors are dithered when they are reduced to the palette.
+    pub fn dither(mut self, dither: Dither) -> SixelOptions {
+        self.dither = dither;
+        self
+    }
+
+    /// Leaves pixels of exactly this color undrawn, so the terminal's
+    /// background shows through them.
+    pub fn transparent(mut self, rgb: [u8; 3]) -> SixelOptions {
+        self.transparent = Some(rgb);
+        self
+    }
+
+    /// Encodes an image. See [`encode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColorConversionError::InvalidRgb`] if `rgb` is not
+    /// `width * height * 3` bytes long.
+    pub fn encode(
+        &self,
+        width: usize,
+        height: usize,
+        rgb: &[u8],
+    ) -> Result<String, ColorConversionError> {
+        check_buffer(width, height, 3, rgb)?;
+        let pixels: Vec<[u8; 3]> = rgb.chunks(3).map(|p| [p[0], p[1], p[2]]).collect();
+        let opaque = |rgb: &[u8; 3]| Some(*rgb) != self.transparent;
+
+        // The color of each register, and the register of each pixel
+        let mut colors: Vec<[u8; 3]> = Vec::new();
+        let mut registers: Vec<Option<usize>> = Vec::with_capacity(pixels.len());
+        let mut distinct: HashMap<[u8; 3], usize> = HashMap::new();
+        for rgb in pixels.iter().filter(|rgb| opaque(rgb)) {
+            if distinct.len() > 256 {
+                break;
+            }
+            let next = distinct.len();
+            distinct.entry(*rgb).or_insert(next);
+        }
+
+        if distinct.len() <= 256 {
+            colors.resize(distinct.len(), [0; 3]);
+            for (&rgb, &register) in &distinct {
+                colors[register] = rgb;
+            }
+            registers.extend(pixels.iter().map(|rgb| opaque(rgb).then(|| distinct[rgb])));
+        } else {
+            let palette = self.palette.clone().unwrap_or_else(Palette::xterm256);
+            let indices = dither(width, height, rgb, &palette, self.dither)?;
+            let mut used: HashMap<u8, usize> = HashMap::new();
+            for (rgb, index) in pixels.iter().zip(indices) {
+                registers.push(opaque(rgb).then(|| {
+                    *used.entry(index).or_insert_with(|| {
+                        colors.push(palette.get(index).unwrap_or_default());
+                        colors.len() - 1
+                    })
+                }));
+            }
+        }
+
+        Ok(encode_registers(
+            width,
+            height,
+            &colors,
+            &registers,
+            self.transparent.is_some(),
+        ))
+    }
+}
+
+/// Writes the sixel sequence for an image whose pixels are given as color
+/// register numbers, or `None` for pixels left undrawn.
+fn encode_registers(
+    width: usize,
+    height: usize,
+    colors: &[[u8; 3]],
+    registers: &[Option<usize>],
+    transparent: bool,
+) -> String {
+    // Aspect ratio 1:1 and the size in pixels, then the color registers,
+    // whose channels are percentages
+    let mut out = format!(
+        "\x1bP0;{};0q\"1;1;{};{}",
+        u8::from(transparent),
+        width,
+        height
+    );
+    for (register, rgb) in colors.iter().enumerate() {
+        let [r, g, b] = rgb.map(|c| (c as u32 * 100 + 127) / 255);
+        let _ = write!(out, "#{};2;{};{};{}", register, r, g, b);
+    }
+
+    for band in (0..height).step_by(6) {
+        if band > 0 {
+            out.push('-');
+        }
+        let rows = band..(band + 6).min(height);
+
+        // Registers in the order they first appear in the band
+        let mut order: Vec<usize> = Vec::new();
+        for y in rows.clone() {
+            for register in registers[y * width..(y + 1) * width].iter().flatten() {
+                if !order.contains(register) {
+                    order.push(*register);
+                }
+            }
+        }
+
+        for (i, &register) in order.iter().enumerate() {
+            if i > 0 {
+                // Back to the start of the band for the next color
+                out.push('$');
+            }
+            let _ = write!(out, "#{}", register);
+            let sixels = (0..width).map(|x| {
+                rows.clone()
+                    .filter(|&y| registers[y * width + x] == Some(register))
+                    .fold(0u8, |bits, y| bits | 1 << (y - band))
+            });
+            push_runs(&mut out, sixels);
+        }
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Writes sixels, compressing runs of four or more into `!count` form and
+/// leaving out empty sixels at the end of the line.
+fn push_runs(out: &mut String, sixels: impl Iterator<Item = u8>) {
+    let mut runs: Vec<(u8, usize)> = Vec::new();
+    for bits in sixels {
+        match runs.last_mut() {
+            Some((last, count)) if *last == bits => *count += 1,
+            _ => runs.push((bits, 1)),
+        }
+    }
+    if runs.last().is_some_and(|&(bits, _)| bits == 0) {
+        runs.pop();
+    }
+
+    for (bits, count) in runs {
+        let c = char::from(63 + bits);
+        if count > 3 {
+            let _ = write!(out, "!{}{}", count, c);
+        } else {
+            out.extend(std::iter::repeat_n(c, count));
+        }
+    }
+}
+
+/// Encodes a raw RGB image as a sixel escape sequence, ready to be printed.
+///
+/// Images with 256 colors or fewer are encoded with their exact colors.
+/// Others are reduced to the xterm 256-color palette with Floyd–Steinberg
+/// dithering; use [`SixelOptions`] to change that or to make a color
+/// transparent. Pixels are square, and repeated sixels are run-length encoded.
+///
+/// # Arguments
+///
+/// * `width` - The width of the image, in pixels.
+/// * `height` - The height of the image, in pixels.
+/// * `rgb` - The pixels, row by row from the top left, as `r, g, b` bytes.
+///
+/// # Errors
+///
+/// Returns [`ColorConversionError::InvalidRgb`] if `rgb` is not
+/// `width * height * 3` bytes long.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::sixel;
+///
+/// // A red pixel above a blue one
+/// let sixel = sixel::encode(1, 2, &[255, 0, 0, 0, 0, 255])?;
+/// assert_eq!(
+///     sixel,
+///     "\x1bP0;0;0q\"1;1;1;2#0;2;100;0;0#1;2;0;0;100#0@$#1A\x1b\\"
+/// );
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+pub fn encode(width: usize, height: usize, rgb: &[u8]) -> Result<String, ColorConversionError> {
+    SixelOptions::new().encode(width, height, rgb)
+}
@@ -0,0 +1,305 @@
+use std::f64::consts::TAU;
+use std::time::{Duration, Instant};
+
+use super::style::Style;
+use crate::color::{
+    hsl_to_rgb, rgb_from_color_definition, rgb_to_hsl, Color, ColorConversionError, ColorDepth,
+    Gradient, IntoRgb,
+};
+
+/// The frames of a spinner animation, shown one after another.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::Frames;
+///
+/// assert_eq!(Frames::LINE.as_slice(), ["-", "\\", "|", "/"]);
+///
+/// const BOUNCE: Frames = Frames::new(&["[=  ]", "[ = ]", "[  =]", "[ = ]"]);
+/// assert_eq!(BOUNCE.len(), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Frames(&'static [&'static str]);
+
+impl Frames {
+    /// `⠋⠙⠹⠸` braille dots chasing each other.
+    pub const DOTS: Frames = Frames::new(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"]);
+    /// `-\|/` plain ASCII, for terminals without Unicode.
+    pub const LINE: Frames = Frames::new(&["-", "\\", "|", "/"]);
+    /// `◜◠◝` a rotating arc.
+    pub const ARC: Frames = Frames::new(&["◜", "◠", "◝", "◞", "◡", "◟"]);
+    /// `⣾⣽⣻` a full braille cell with one dot missing.
+    pub const BRAILLE: Frames = Frames::new(&["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"]);
+
+    /// Creates a set of frames.
+    pub const fn new(frames: &'static [&'static str]) -> Frames {
+        Frames(frames)
+    }
+
+    /// Returns the frames, in order.
+    pub const fn as_slice(&self) -> &'static [&'static str] {
+        self.0
+    }
+
+    /// Returns the number of frames.
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no frames.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Default for Frames {
+    fn default() -> Self {
+        Frames::DOTS
+    }
+}
+
+/// How a [`ColorCycle`] moves through its colors.
+#[derive(Debug, Clone, PartialEq)]
+enum CycleKind {
+    /// Between a color's own lightness and a dimmer shade of it.
+    Pulse {
+        hue: f64,
+        saturation: f64,
+        lightness: f64,
+        dim: f64,
+    },
+    /// Back and forth along a gradient.
+    Gradient(Gradient),
+    /// Once around the color wheel.
+    Hue { start: f64 },
+}
+
+/// A color that changes with each tick of an animation and repeats every
+/// `period` ticks.
+///
+/// Colors are computed from tick counts rather than read from a clock, so
+/// animations can be tested by passing in ticks. Use a [`Ticker`] to turn
+/// elapsed time into ticks.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Color;
+/// use terminal_style::format::ColorCycle;
+///
+/// let pulse = ColorCycle::pulse("#1E90FF", 20)?;
+/// assert_eq!(pulse.color_at(0), Color::Rgb([30, 144, 255]));
+/// assert_eq!(pulse.color_at(20), Color::Rgb([30, 144, 255]));
+/// assert_ne!(pulse.color_at(10), Color::Rgb([30, 144, 255]));
+/// # Ok::<(), terminal_style::color::ColorConversionError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorCycle {
+    kind: CycleKind,
+    period: u64,
+    depth: ColorDepth,
+}
+
+impl ColorCycle {
+    /// Pulses a color: it starts at `color`, dims smoothly to 40% of its
+    /// lightness halfway through the period, and brightens back again.
+    ///
+    /// The lightness follows a sine curve, so the pulse slows down at either
+    /// end instead of bouncing.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - Any type implementing [`IntoRgb`] (e.g., `#RRGGBB`, `[r, g, b]`, or `u8` ANSI).
+    /// * `period` - The number of ticks in one pulse.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColorConversionError`] if the color is invalid.
+    pub fn pulse<C: IntoRgb>(color: C, period: u64) -> Result<ColorCycle, ColorConversionError> {
+        let (hue, saturation, lightness) = rgb_to_hsl(rgb_from_color_definition(color)?);
+        Ok(ColorCycle::new(
+            CycleKind::Pulse {
+                hue,
+                saturation,
+                lightness,
+                dim: lightness * 0.4,
+            },
+            period,
+        ))
+    }
+
+    /// Moves from the start of `gradient` to its end and back, easing in and
+    /// out at either end.
+    pub fn gradient(gradient: Gradient, period: u64) -> ColorCycle {
+        ColorCycle::new(CycleKind::Gradient(gradient), period)
+    }
+
+    /// Turns once around the color wheel, starting from `start_hue` degrees,
+    /// at full saturation.
+    pub fn hue(start_hue: f64, period: u64) -> ColorCycle {
+        ColorCycle::new(CycleKind::Hue { start: start_hue }, period)
+    }
+
+    fn new(kind: CycleKind, period: u64) -> ColorCycle {
+        ColorCycle {
+            kind,
+            period: period.max(1),
+            depth: ColorDepth::TrueColor,
+        }
+    }
+
+    /// Sets whether the colors are TrueColor or from the 256-color palette.
+    ///
+    /// Defaults to [`ColorDepth::TrueColor`].
+    pub fn color_depth(mut self, depth: ColorDepth) -> ColorCycle {
+        self.depth = depth;
+        self
+    }
+
+    /// Returns the color at `tick`.
+    pub fn color_at(&self, tick: u64) -> Color {
+        let phase = (tick % self.period) as f64 / self.period as f64;
+        // 0 at the start of the period, 1 halfway through, easing in and out.
+        // Folding the phase keeps both halves exactly symmetric.
+        let eased = (1.0 - (TAU * phase.min(1.0 - phase)).cos()) / 2.0;
+
+        let rgb = match &self.kind {
+            CycleKind::Pulse {
+                hue,
+                saturation,
+                lightness,
+                dim,
+            } => {
+                if eased == 0.0 {
+                    // Return the exact color rather than its HSL round trip
+                    hsl_to_rgb(*hue, *saturation, *lightness)
+                } else {
+                    hsl_to_rgb(*hue, *saturation, lightness + (dim - lightness) * eased)
+                }
+            }
+            CycleKind::Gradient(gradient) => gradient.at(eased),
+            CycleKind::Hue { start } => hsl_to_rgb(start + 360.0 * phase, 1.0, 0.5),
+        };
+        self.depth.color(rgb)
+    }
+}
+
+/// Converts elapsed time into animation ticks of a fixed length.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use terminal_style::format::Ticker;
+///
+/// let ticker = Ticker::new(Duration::from_millis(80));
+/// assert_eq!(ticker.tick_at(Duration::from_millis(250)), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ticker {
+    start: Instant,
+    interval: Duration,
+}
+
+impl Ticker {
+    /// Starts a ticker that advances once every `interval`.
+    pub fn new(interval: Duration) -> Ticker {
+        Ticker {
+            start: Instant::now(),
+            interval,
+        }
+    }
+
+    /// Returns the number of whole ticks since the ticker was created.
+    pub fn tick(&self) -> u64 {
+        self.tick_at(self.start.elapsed())
+    }
+
+    /// Returns the number of whole ticks in `elapsed`.
+    pub fn tick_at(&self, elapsed: Duration) -> u64 {
+        (elapsed.as_nanos() / self.interval.as_nanos().max(1)) as u64
+    }
+}
+
+/// An animated spinner that yields one styled frame per tick.
+///
+/// The frame at a given tick is available from [`frame`](Self::frame), which
+/// does not depend on a clock. [`next_frame`](Self::next_frame), and the
+/// [`Iterator`] implementation, step through the frames in order.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{ColorCycle, Frames, Spinner, Style};
+///
+/// let mut spinner = Spinner::new(Frames::LINE).style(Style::new().bold());
+/// assert_eq!(spinner.next_frame(), "\x1b[1m-\x1b[0m");
+/// assert_eq!(spinner.next_frame(), "\x1b[1m\\\x1b[0m");
+/// assert_eq!(spinner.frame(6), "\x1b[1m|\x1b[0m");
+///
+/// let rainbow = Spinner::new(Frames::ARC).colors(ColorCycle::hue(0.0, 12));
+/// assert_eq!(rainbow.frame(0), "\x1b[38;2;255;0;0m◜\x1b[0m");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spinner {
+    frames: Frames,
+    style: Style,
+    colors: Option<ColorCycle>,
+    tick: u64,
+}
+
+impl Spinner {
+    /// Creates an unstyled spinner starting at its first frame.
+    pub fn new(frames: Frames) -> Spinner {
+        Spinner {
+            frames,
+            style: Style::new(),
+            colors: None,
+            tick: 0,
+        }
+    }
+
+    /// Sets the style the frames are drawn in.
+    pub fn style(mut self, style: Style) -> Spinner {
+        self.style = style;
+        self
+    }
+
+    /// Animates the foreground color of the frames, replacing the style's
+    /// foreground color.
+    pub fn colors(mut self, colors: ColorCycle) -> Spinner {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Returns the styled frame shown at `tick`.
+    pub fn frame(&self, tick: u64) -> String {
+        let frames = self.frames.as_slice();
+        if frames.is_empty() {
+            return String::new();
+        }
+        let frame = frames[(tick % frames.len() as u64) as usize];
+        let style = match &self.colors {
+            Some(colors) => self.style.fg(colors.color_at(tick)),
+            None => self.style,
+        };
+        style.paint(frame)
+    }
+
+    /// Returns the styled frame for the current tick, and advances to the next.
+    pub fn next_frame(&mut self) -> String {
+        let frame = self.frame(self.tick);
+        self.tick = self.tick.wrapping_add(1);
+        frame
+    }
+}
+
+/// Never ends; the frames repeat.
+impl Iterator for Spinner {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        Some(self.next_frame())
+    }
+}
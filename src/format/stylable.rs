@@ -1,7 +1,21 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, VecDeque},
+    hash::{BuildHasher, Hash},
+    rc::Rc,
+    sync::Arc,
+};
+
 /// A trait for applying terminal styling to different types of text data.
 ///
 /// `Stylable` allows formatting functions to operate on single strings,
 /// flat vectors, or even 2D vectors (grids) of strings.
+///
+/// The string types (`str`, `String`, `Cow<str>`, `Box<str>`, `Rc<str>`,
+/// `Arc<str>`) style to a `String`. Containers are implemented generically
+/// and recurse into their elements, so any nesting of slices, arrays, `Vec`s,
+/// `Option`s and references works, e.g. `Vec<&str>`, `&[String]` or
+/// `[[&str; 3]; 2]`. Maps style their values and keep their keys.
 pub trait Stylable {
     /// The resulting type after styling is applied.
     type Output;
@@ -22,9 +36,9 @@ pub trait Stylable {
         E: std::fmt::Debug;
 }
 
-// --- Implement Stylable ---
+// --- Implement Stylable for strings ---
 
-impl Stylable for String {
+impl Stylable for str {
     type Output = String;
 
     fn apply<F>(&self, f: F) -> Self::Output
@@ -43,7 +57,7 @@ impl Stylable for String {
     }
 }
 
-impl Stylable for &str {
+impl Stylable for String {
     type Output = String;
 
     fn apply<F>(&self, f: F) -> Self::Output
@@ -62,15 +76,47 @@ impl Stylable for &str {
     }
 }
 
-// Implement Stylable for &String
-impl Stylable for &String {
-    type Output = String;
+// --- Forward references and smart pointers to their target ---
+
+/// Implements forwarding from a pointer type to the `Stylable` it points to.
+macro_rules! forward_stylable {
+    ($($pointer:ty),* $(,)?) => {
+        $(
+            impl<T: Stylable + ?Sized> Stylable for $pointer {
+                type Output = T::Output;
+
+                fn apply<F>(&self, f: F) -> Self::Output
+                where
+                    F: Fn(&str) -> String,
+                {
+                    (**self).apply(f)
+                }
+
+                fn apply_result<F, E>(&self, f: F) -> Result<Self::Output, E>
+                where
+                    F: Fn(&str) -> Result<String, E>,
+                    E: std::fmt::Debug,
+                {
+                    (**self).apply_result(f)
+                }
+            }
+        )*
+    };
+}
+
+forward_stylable!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
+impl<B> Stylable for Cow<'_, B>
+where
+    B: Stylable + ToOwned + ?Sized,
+{
+    type Output = B::Output;
 
     fn apply<F>(&self, f: F) -> Self::Output
     where
         F: Fn(&str) -> String,
     {
-        f(self.as_str())
+        (**self).apply(f)
     }
 
     fn apply_result<F, E>(&self, f: F) -> Result<Self::Output, E>
@@ -78,18 +124,20 @@ impl Stylable for &String {
         F: Fn(&str) -> Result<String, E>,
         E: std::fmt::Debug,
     {
-        f(self.as_str())
+        (**self).apply_result(f)
     }
 }
 
-impl Stylable for Vec<String> {
-    type Output = Vec<String>;
+// --- Implement Stylable for containers, recursing into each element ---
+
+impl<T: Stylable> Stylable for [T] {
+    type Output = Vec<T::Output>;
 
     fn apply<F>(&self, f: F) -> Self::Output
     where
         F: Fn(&str) -> String,
     {
-        self.iter().map(|s| f(s)).collect()
+        self.iter().map(|item| item.apply(&f)).collect()
     }
 
     fn apply_result<F, E>(&self, f: F) -> Result<Self::Output, E>
@@ -98,23 +146,21 @@ impl Stylable for Vec<String> {
         E: std::fmt::Debug,
     {
         let mut out = Vec::with_capacity(self.len());
-        for s in self {
-            out.push(f(s)?);
+        for item in self {
+            out.push(item.apply_result(&f)?);
         }
         Ok(out)
     }
 }
 
-impl Stylable for Vec<Vec<String>> {
-    type Output = Vec<Vec<String>>;
+impl<T: Stylable> Stylable for Vec<T> {
+    type Output = Vec<T::Output>;
 
     fn apply<F>(&self, f: F) -> Self::Output
     where
         F: Fn(&str) -> String,
     {
-        self.iter()
-            .map(|row| row.iter().map(|s| f(s)).collect())
-            .collect()
+        self.as_slice().apply(f)
     }
 
     fn apply_result<F, E>(&self, f: F) -> Result<Self::Output, E>
@@ -122,27 +168,60 @@ impl Stylable for Vec<Vec<String>> {
         F: Fn(&str) -> Result<String, E>,
         E: std::fmt::Debug,
     {
-        let mut out = Vec::with_capacity(self.len());
-        for row in self {
-            let mut new_row = Vec::with_capacity(row.len());
-            for s in row {
-                new_row.push(f(s)?);
-            }
-            out.push(new_row);
+        self.as_slice().apply_result(f)
+    }
+}
+
+impl<T: Stylable, const N: usize> Stylable for [T; N] {
+    type Output = [T::Output; N];
+
+    fn apply<F>(&self, f: F) -> Self::Output
+    where
+        F: Fn(&str) -> String,
+    {
+        self.each_ref().map(|item| item.apply(&f))
+    }
+
+    fn apply_result<F, E>(&self, f: F) -> Result<Self::Output, E>
+    where
+        F: Fn(&str) -> Result<String, E>,
+        E: std::fmt::Debug,
+    {
+        let out = self.as_slice().apply_result(f)?;
+        match out.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => unreachable!("styled output has the same length as the input array"),
         }
-        Ok(out)
     }
 }
 
-// Forward &Vec<String> to Vec<String>
-impl Stylable for &Vec<String> {
-    type Output = Vec<String>;
+impl<T: Stylable> Stylable for Option<T> {
+    type Output = Option<T::Output>;
+
+    fn apply<F>(&self, f: F) -> Self::Output
+    where
+        F: Fn(&str) -> String,
+    {
+        self.as_ref().map(|item| item.apply(f))
+    }
+
+    fn apply_result<F, E>(&self, f: F) -> Result<Self::Output, E>
+    where
+        F: Fn(&str) -> Result<String, E>,
+        E: std::fmt::Debug,
+    {
+        self.as_ref().map(|item| item.apply_result(f)).transpose()
+    }
+}
+
+impl<T: Stylable> Stylable for VecDeque<T> {
+    type Output = VecDeque<T::Output>;
 
     fn apply<F>(&self, f: F) -> Self::Output
     where
         F: Fn(&str) -> String,
     {
-        (*self).apply(f)
+        self.iter().map(|item| item.apply(&f)).collect()
     }
 
     fn apply_result<F, E>(&self, f: F) -> Result<Self::Output, E>
@@ -150,19 +229,27 @@ impl Stylable for &Vec<String> {
         F: Fn(&str) -> Result<String, E>,
         E: std::fmt::Debug,
     {
-        (*self).apply_result(f)
+        self.iter().map(|item| item.apply_result(&f)).collect()
     }
 }
 
-// Forward &Vec<Vec<String>> to Vec<Vec<String>>
-impl Stylable for &Vec<Vec<String>> {
-    type Output = Vec<Vec<String>>;
+impl<K, V, S> Stylable for HashMap<K, V, S>
+where
+    K: Clone + Eq + Hash,
+    V: Stylable,
+    S: BuildHasher + Clone,
+{
+    type Output = HashMap<K, V::Output, S>;
 
     fn apply<F>(&self, f: F) -> Self::Output
     where
         F: Fn(&str) -> String,
     {
-        (*self).apply(f)
+        let mut out = HashMap::with_capacity_and_hasher(self.len(), self.hasher().clone());
+        for (key, value) in self {
+            out.insert(key.clone(), value.apply(&f));
+        }
+        out
     }
 
     fn apply_result<F, E>(&self, f: F) -> Result<Self::Output, E>
@@ -170,6 +257,37 @@ impl Stylable for &Vec<Vec<String>> {
         F: Fn(&str) -> Result<String, E>,
         E: std::fmt::Debug,
     {
-        (*self).apply_result(f)
+        let mut out = HashMap::with_capacity_and_hasher(self.len(), self.hasher().clone());
+        for (key, value) in self {
+            out.insert(key.clone(), value.apply_result(&f)?);
+        }
+        Ok(out)
+    }
+}
+
+impl<K, V> Stylable for BTreeMap<K, V>
+where
+    K: Clone + Ord,
+    V: Stylable,
+{
+    type Output = BTreeMap<K, V::Output>;
+
+    fn apply<F>(&self, f: F) -> Self::Output
+    where
+        F: Fn(&str) -> String,
+    {
+        self.iter()
+            .map(|(key, value)| (key.clone(), value.apply(&f)))
+            .collect()
+    }
+
+    fn apply_result<F, E>(&self, f: F) -> Result<Self::Output, E>
+    where
+        F: Fn(&str) -> Result<String, E>,
+        E: std::fmt::Debug,
+    {
+        self.iter()
+            .map(|(key, value)| Ok((key.clone(), value.apply_result(&f)?)))
+            .collect()
     }
 }
@@ -0,0 +1,432 @@
+use std::{fmt, str::FromStr};
+
+use super::stylable::Stylable;
+use crate::color::{
+    value::{name_eq, SpecError},
+    Color, ColorConversionError,
+};
+
+/// A complete text style: optional foreground and background colors plus SGR attributes.
+///
+/// `Style` is the reusable counterpart of the single-purpose functions such as
+/// [`bold`](super::bold) and [`color_native`](super::color_native). Colors are
+/// kept as [`Color`] values and emitted in the form they were specified in.
+///
+/// Styles can be built with `const` builder methods, or parsed from a
+/// whitespace separated spec such as `"bold underline #FF1493 on #222"`:
+/// attribute names, an optional foreground color, and an optional background
+/// color introduced by `on`. Hex colors need their leading `#` here, and
+/// giving either color twice is an error.
+///
+/// # Examples
+///
+/// ```
+/// use terminal_style::color::Color;
+/// use terminal_style::format::Style;
+///
+/// let warning = Style::new().bold().fg(Color::Ansi16(3));
+/// assert_eq!(warning.paint("Careful"), "\x1b[1;33mCareful\x1b[0m");
+///
+/// let parsed: Style = "bold yellow".parse().unwrap();
+/// assert_eq!(parsed, warning);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    /// Foreground color, or `None` to leave it unchanged.
+    pub fg: Option<Color>,
+    /// Background color, or `None` to leave it unchanged.
+    pub bg: Option<Color>,
+    /// SGR 1.
+    pub bold: bool,
+    /// SGR 2.
+    pub faint: bool,
+    /// SGR 3.
+    pub italic: bool,
+    /// SGR 4.
+    pub underline: bool,
+    /// SGR 5.
+    pub blink: bool,
+    /// SGR 7.
+    pub inverse: bool,
+    /// SGR 8.
+    pub hidden: bool,
+    /// SGR 9.
+    pub strikethrough: bool,
+}
+
+/// Attribute names in SGR order, paired with their codes.
+const ATTRIBUTES: [(&str, u8); 8] = [
+    ("bold", 1),
+    ("faint", 2),
+    ("italic", 3),
+    ("underline", 4),
+    ("blink", 5),
+    ("inverse", 7),
+    ("hidden", 8),
+    ("strikethrough", 9),
+];
+
+/// Looks up an attribute name or alias, returning its index in [`ATTRIBUTES`].
+const fn attribute_index(word: &[u8]) -> Option<usize> {
+    if name_eq(word, b"dim") {
+        return Some(1);
+    }
+    if name_eq(word, b"reverse") {
+        return Some(5);
+    }
+    if name_eq(word, b"strike") {
+        return Some(7);
+    }
+
+    let mut index = 0;
+    while index < ATTRIBUTES.len() {
+        if name_eq(word, ATTRIBUTES[index].0.as_bytes()) {
+            return Some(index);
+        }
+        index += 1;
+    }
+    None
+}
+
+impl Style {
+    /// Creates a plain style with no colors and no attributes.
+    pub const fn new() -> Style {
+        Style {
+            fg: None,
+            bg: None,
+            bold: false,
+            faint: false,
+            italic: false,
+            underline: false,
+            blink: false,
+            inverse: false,
+            hidden: false,
+            strikethrough: false,
+        }
+    }
+
+    /// Sets the foreground color.
+    pub const fn fg(mut self, color: Color) -> Style {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Sets the background color.
+    pub const fn bg(mut self, color: Color) -> Style {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Enables **bold**.
+    pub const fn bold(mut self) -> Style {
+        self.bold = true;
+        self
+    }
+
+    /// Enables faint (decreased intensity).
+    pub const fn faint(mut self) -> Style {
+        self.faint = true;
+        self
+    }
+
+    /// Enables *italic*.
+    pub const fn italic(mut self) -> Style {
+        self.italic = true;
+        self
+    }
+
+    /// Enables underline.
+    pub const fn underline(mut self) -> Style {
+        self.underline = true;
+        self
+    }
+
+    /// Enables blinking.
+    pub const fn blink(mut self) -> Style {
+        self.blink = true;
+        self
+    }
+
+    /// Enables inverse (swapped foreground/background).
+    pub const fn inverse(mut self) -> Style {
+        self.inverse = true;
+        self
+    }
+
+    /// Enables hidden (invisible) text.
+    pub const fn hidden(mut self) -> Style {
+        self.hidden = true;
+        self
+    }
+
+    /// Enables strikethrough.
+    pub const fn strikethrough(mut self) -> Style {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Returns a copy of the style with the attribute at `index` in [`ATTRIBUTES`] enabled.
+    const fn with_flag(mut self, index: usize) -> Style {
+        match index {
+            0 => self.bold = true,
+            1 => self.faint = true,
+            2 => self.italic = true,
+            3 => self.underline = true,
+            4 => self.blink = true,
+            5 => self.inverse = true,
+            6 => self.hidden = true,
+            _ => self.strikethrough = true,
+        }
+        self
+    }
+
+    /// Parses a style spec in a `const` context.
+    ///
+    /// This is the parser behind [`Style::from_str`] and the [`style!`](crate::style)
+    /// macro. On failure, returns the error along with the byte range of the
+    /// offending word.
+    pub(crate) const fn parse_spec(spec: &[u8]) -> Result<Style, (SpecError, usize, usize)> {
+        let mut style = Style::new();
+        let mut pending_on: Option<(usize, usize)> = None;
+        let mut pos = 0;
+
+        loop {
+            while pos < spec.len() && spec[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            if pos == spec.len() {
+                break;
+            }
+
+            let start = pos;
+            while pos < spec.len() && !spec[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            let word = spec.split_at(start).1.split_at(pos - start).0;
+
+            if let Some((on_start, _)) = pending_on {
+                if style.bg.is_some() {
+                    return Err((SpecError::DuplicateBackground, on_start, pos));
+                }
+                match Color::parse_style_spec(word) {
+                    Ok(color) => style.bg = Some(color),
+                    Err(err) => return Err((err, start, pos)),
+                }
+                pending_on = None;
+            } else if name_eq(word, b"on") {
+                pending_on = Some((start, pos));
+            } else if let Some(index) = attribute_index(word) {
+                style = style.with_flag(index);
+            } else if !name_eq(word, b"none") {
+                match Color::parse_style_spec(word) {
+                    Ok(_) if style.fg.is_some() => {
+                        return Err((SpecError::DuplicateForeground, start, pos))
+                    }
+                    Ok(color) => style.fg = Some(color),
+                    Err(err) => return Err((err, start, pos)),
+                }
+            }
+        }
+
+        match pending_on {
+            Some((start, end)) => Err((SpecError::MissingBackground, start, end)),
+            None => Ok(style),
+        }
+    }
+
+    /// Returns `true` if the style sets no colors and no attributes.
+    pub fn is_plain(&self) -> bool {
+        *self == Style::new()
+    }
+
+    /// Returns the attribute flags in the same order as [`ATTRIBUTES`].
+    fn flags(&self) -> [bool; 8] {
+        [
+            self.bold,
+            self.faint,
+            self.italic,
+            self.underline,
+            self.blink,
+            self.inverse,
+            self.hidden,
+            self.strikethrough,
+        ]
+    }
+
+    /// Returns the SGR parameters for this style, joined with `;`.
+    ///
+    /// Attributes come first, followed by the foreground and background colors.
+    /// A plain style has no parameters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminal_style::color::Color;
+    /// use terminal_style::format::Style;
+    ///
+    /// let style = Style::new().underline().bg(Color::Ansi256(22));
+    /// assert_eq!(style.params(), "4;48;5;22");
+    /// ```
+    pub fn params(&self) -> String {
+        let mut params: Vec<String> = ATTRIBUTES
+            .iter()
+            .zip(self.flags())
+            .filter(|(_, enabled)| *enabled)
+            .map(|((_, code), _)| code.to_string())
+            .collect();
+
+        if let Some(fg) = self.fg {
+            params.push(fg.fg_params());
+        }
+        if let Some(bg) = self.bg {
+            params.push(bg.bg_params());
+        }
+
+        params.join(";")
+    }
+
+    /// Returns the escape sequence that turns this style on, or an empty
+    /// string for a plain style.
+    pub fn prefix(&self) -> String {
+        if self.is_plain() {
+            String::new()
+        } else {
+            format!("\x1b[{}m", self.params())
+        }
+    }
+
+    /// Styles a single string, ending it with a reset.
+    ///
+    /// A plain style returns the text unchanged.
+    pub fn paint(&self, text: &str) -> String {
+        if self.is_plain() {
+            text.to_string()
+        } else {
+            format!("{}{}\x1b[0m", self.prefix(), text)
+        }
+    }
+
+    /// Applies the style to any [`Stylable`] input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use terminal_style::format::Style;
+    ///
+    /// let rows = vec!["a".to_string(), "b".to_string()];
+    /// let styled = Style::new().italic().apply(&rows);
+    /// assert_eq!(styled, vec!["\x1b[3ma\x1b[0m", "\x1b[3mb\x1b[0m"]);
+    /// ```
+    pub fn apply<T: Stylable>(&self, input: T) -> T::Output {
+        input.apply(|s| self.paint(s))
+    }
+}
+
+impl fmt::Display for Style {
+    /// Formats the style as a spec string that [`Style::from_str`] parses back.
+    /// A plain style is formatted as `none`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words: Vec<String> = ATTRIBUTES
+            .iter()
+            .zip(self.flags())
+            .filter(|(_, enabled)| *enabled)
+            .map(|((name, _), _)| name.to_string())
+            .collect();
+
+        if let Some(fg) = self.fg {
+            words.push(fg.to_string());
+        }
+        if let Some(bg) = self.bg {
+            words.push(format!("on {}", bg));
+        }
+
+        if words.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", words.join(" "))
+        }
+    }
+}
+
+impl FromStr for Style {
+    type Err = ColorConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Style::parse_spec(s.as_bytes()).map_err(|(err, start, end)| match err {
+            SpecError::MissingBackground
+            | SpecError::DuplicateForeground
+            | SpecError::DuplicateBackground => err.into_error(s),
+            _ => err.into_error(&s[start..end]),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::{Style, ATTRIBUTES};
+    use crate::color::Color;
+    use serde::de::{self, MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt;
+
+    /// Serializes a style as its spec string, e.g. `"bold #FF1493 on #222222"`.
+    impl Serialize for Style {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    /// Deserializes a style from a spec string, or from a map with optional
+    /// `fg` and `bg` colors and boolean attribute fields.
+    impl<'de> Deserialize<'de> for Style {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(StyleVisitor)
+        }
+    }
+
+    struct StyleVisitor;
+
+    impl<'de> Visitor<'de> for StyleVisitor {
+        type Value = Style;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a style string or a map of style fields")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Style, E> {
+            v.parse().map_err(E::custom)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Style, A::Error> {
+            const FIELDS: &[&str] = &[
+                "fg",
+                "bg",
+                "bold",
+                "faint",
+                "italic",
+                "underline",
+                "blink",
+                "inverse",
+                "hidden",
+                "strikethrough",
+            ];
+
+            let mut style = Style::new();
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "fg" => style.fg = map.next_value::<Option<Color>>()?,
+                    "bg" => style.bg = map.next_value::<Option<Color>>()?,
+                    other => match ATTRIBUTES.iter().position(|(name, _)| *name == other) {
+                        Some(index) => {
+                            if map.next_value()? {
+                                style = style.with_flag(index);
+                            }
+                        }
+                        None => return Err(de::Error::unknown_field(other, FIELDS)),
+                    },
+                }
+            }
+            Ok(style)
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use super::{
+    border::{Border, BorderChars},
+    layout::{pad, HAlign},
+    sgr,
+    style::Style,
+    width::{truncate, visible_width, wrap},
+};
+use crate::color::Color;
+
+/// The lines drawn around and between the cells of a [`Table`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableBorder {
+    /// No lines; columns are separated by spaces.
+    None,
+    /// Box-drawing or ASCII lines, as used by [`Border`].
+    Lines(BorderChars),
+    /// A GitHub-flavored Markdown table. The first row is always the header.
+    /// `|` in cells is escaped as `\|`, and lines within a cell are joined
+    /// with `<br>`.
+    Markdown,
+}
+
+impl Default for TableBorder {
+    fn default() -> Self {
+        TableBorder::Lines(BorderChars::SINGLE)
+    }
+}
+
+impl From<Border> for TableBorder {
+    fn from(border: Border) -> Self {
+        match border.chars() {
+            Some(chars) => TableBorder::Lines(chars),
+            None => TableBorder::None,
+        }
+    }
+}
+
+/// What to do with cells wider than their column's maximum width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Overflow {
+    /// Wrap the text onto more lines, breaking at spaces where possible.
+    #[default]
+    Wrap,
+    /// Cut the text off, ending it with `…`.
+    Truncate,
+}
+
+/// Describes how [`table`] lays out and draws rows of cells.
+///
+/// By default a table has single-line borders, left-aligned columns as wide
+/// as their widest cell, one space of padding on either side of each cell,
+/// and no header.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Color;
+/// use terminal_style::format::{Border, HAlign, Overflow, Style, Table};
+///
+/// let options = Table::new()
+///     .border(Border::Rounded)
+///     .header(Style::new().bold())
+///     .align(2, HAlign::Right)
+///     .max_width(1, 30)
+///     .overflow(Overflow::Truncate)
+///     .row_backgrounds([Color::Default, Color::Ansi256(236)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Table {
+    border: TableBorder,
+    border_style: Style,
+    header: Option<Style>,
+    align: Vec<HAlign>,
+    max_width: Vec<Option<usize>>,
+    overflow: Overflow,
+    row_backgrounds: Vec<Color>,
+    padding: Option<usize>,
+}
+
+impl Table {
+    /// Creates the default table options.
+    pub fn new() -> Table {
+        Table::default()
+    }
+
+    /// Sets the lines drawn around and between cells: a [`Border`], or a [`TableBorder`].
+    pub fn border(mut self, border: impl Into<TableBorder>) -> Table {
+        self.border = border.into();
+        self
+    }
+
+    /// Sets the style the border is drawn in, e.g. its color.
+    pub fn border_style(mut self, style: Style) -> Table {
+        self.border_style = style;
+        self
+    }
+
+    /// Treats the first row as a header, drawn in `style` and separated from the other rows.
+    ///
+    /// Header cells that are already styled keep their own styling on top of `style`.
+    pub fn header(mut self, style: Style) -> Table {
+        self.header = Some(style);
+        self
+    }
+
+    /// Sets the alignment of column `col` (counted from 0).
+    pub fn align(mut self, col: usize, align: HAlign) -> Table {
+        if self.align.len() <= col {
+            self.align.resize(col + 1, HAlign::Left);
+        }
+        self.align[col] = align;
+        self
+    }
+
+    /// Limits column `col` (counted from 0) to `width` columns of text,
+    /// handling longer cells as set by [`overflow`](Self::overflow).
+    pub fn max_width(mut self, col: usize, width: usize) -> Table {
+        if self.max_width.len() <= col {
+            self.max_width.resize(col + 1, None);
+        }
+        self.max_width[col] = Some(width.max(1));
+        self
+    }
+
+    /// Sets how cells wider than their column's maximum width are handled.
+    pub fn overflow(mut self, overflow: Overflow) -> Table {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Colors the backgrounds of the rows below the header, cycling through
+    /// `colors`. Use [`Color::Default`] to leave a row's background alone.
+    pub fn row_backgrounds(mut self, colors: impl IntoIterator<Item = Color>) -> Table {
+        self.row_backgrounds = colors.into_iter().collect();
+        self
+    }
+
+    /// Sets the number of spaces on either side of each cell's content.
+    ///
+    /// Defaults to 1. Without a border, columns are separated by twice the
+    /// padding, or one space if the padding is 0.
+    pub fn padding(mut self, padding: usize) -> Table {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Renders rows of cells. See [`table`].
+    pub fn render<R, S>(&self, rows: &[R]) -> Vec<String>
+    where
+        R: AsRef<[S]>,
+        S: AsRef<str>,
+    {
+        let columns = rows.iter().map(|row| row.as_ref().len()).max().unwrap_or(0);
+        if columns == 0 {
+            return Vec::new();
+        }
+
+        // Split each cell into lines that fit its column
+        let cells: Vec<Vec<Vec<String>>> = rows
+            .iter()
+            .map(|row| {
+                let row = row.as_ref();
+                (0..columns)
+                    .map(|col| {
+                        let cell = row.get(col).map_or("", |cell| cell.as_ref());
+                        if self.border == TableBorder::Markdown {
+                            // A Markdown row is a single line, and `|` would end the cell
+                            vec![self.fit(&cell.replace('|', "\\|"), col).join("<br>")]
+                        } else {
+                            self.fit(cell, col)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let widths: Vec<usize> = (0..columns)
+            .map(|col| {
+                cells
+                    .iter()
+                    .flat_map(|row| row[col].iter())
+                    .map(|line| visible_width(line))
+                    .max()
+                    .unwrap_or(0)
+                    .max(1)
+            })
+            .collect();
+
+        let has_header = self.header.is_some() || self.border == TableBorder::Markdown;
+        let padding = self.padding.unwrap_or(1);
+        let edge = |text: String| self.border_style.paint(&text);
+
+        let mut lines = Vec::new();
+        if let TableBorder::Lines(chars) = self.border {
+            lines.push(edge(self.rule(&widths, padding, chars, Rule::Top)));
+        }
+
+        for (index, row) in cells.iter().enumerate() {
+            let base = if index == 0 && has_header {
+                self.header
+            } else {
+                let body_index = index - usize::from(has_header);
+                match self
+                    .row_backgrounds
+                    .get(body_index % self.row_backgrounds.len().max(1))
+                {
+                    Some(Color::Default) | None => None,
+                    Some(&color) => Some(Style::new().bg(color)),
+                }
+            };
+
+            let height = row.iter().map(Vec::len).max().unwrap_or(1);
+            for line in 0..height {
+                let segments = row.iter().enumerate().map(|(col, cell)| {
+                    let content = cell.get(line).map_or("", String::as_str);
+                    let align = self.align.get(col).copied().unwrap_or_default();
+                    let padded = pad(content, widths[col], align);
+                    let segment = match self.border {
+                        TableBorder::None => padded,
+                        _ => format!("{0}{1}{0}", " ".repeat(padding), padded),
+                    };
+                    match base {
+                        Some(base) => sgr::restyle(&segment, |_, _, current| base.patch(&current)),
+                        None => segment,
+                    }
+                });
+                let segments: Vec<String> = segments.collect();
+
+                lines.push(match self.border {
+                    TableBorder::None => segments.join(&" ".repeat((padding * 2).max(1))),
+                    TableBorder::Lines(chars) => {
+                        let vertical = edge(chars.vertical.to_string());
+                        format!("{0}{1}{0}", vertical, segments.join(&vertical))
+                    }
+                    TableBorder::Markdown => {
+                        let vertical = edge("|".to_string());
+                        format!("{0}{1}{0}", vertical, segments.join(&vertical))
+                    }
+                });
+            }
+
+            if index == 0 && has_header {
+                match self.border {
+                    TableBorder::Lines(chars) => {
+                        lines.push(edge(self.rule(&widths, padding, chars, Rule::Middle)))
+                    }
+                    TableBorder::Markdown => lines.push(edge(self.markdown_rule(&widths, padding))),
+                    TableBorder::None => {}
+                }
+            }
+        }
+
+        if let TableBorder::Lines(chars) = self.border {
+            lines.push(edge(self.rule(&widths, padding, chars, Rule::Bottom)));
+        }
+        lines
+    }
+
+    /// Splits a cell into lines, wrapping or truncating them to the column's maximum width.
+    fn fit(&self, cell: &str, col: usize) -> Vec<String> {
+        match self.max_width.get(col).copied().flatten() {
+            Some(max) if self.overflow == Overflow::Wrap => wrap(cell, max),
+            Some(max) => cell.split('\n').map(|line| ellipsize(line, max)).collect(),
+            None => cell.split('\n').map(str::to_string).collect(),
+        }
+    }
+
+    /// Draws a horizontal line across the table.
+    fn rule(&self, widths: &[usize], padding: usize, chars: BorderChars, rule: Rule) -> String {
+        let (left, junction, right) = match rule {
+            Rule::Top => (chars.top_left, chars.top_junction, chars.top_right),
+            Rule::Middle => (chars.left_junction, chars.cross, chars.right_junction),
+            Rule::Bottom => (chars.bottom_left, chars.bottom_junction, chars.bottom_right),
+        };
+        let segments: Vec<String> = widths
+            .iter()
+            .map(|width| chars.horizontal.to_string().repeat(width + padding * 2))
+            .collect();
+        format!("{}{}{}", left, segments.join(&junction.to_string()), right)
+    }
+
+    /// Draws the line under a Markdown header, marking each column's alignment.
+    fn markdown_rule(&self, widths: &[usize], padding: usize) -> String {
+        let segments: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(col, width)| {
+                let dashes = (width + padding * 2).max(3);
+                match self.align.get(col).copied().unwrap_or_default() {
+                    HAlign::Left => "-".repeat(dashes),
+                    HAlign::Center => format!(":{}:", "-".repeat(dashes - 2)),
+                    HAlign::Right => format!("{}:", "-".repeat(dashes - 1)),
+                }
+            })
+            .collect();
+        format!("|{}|", segments.join("|"))
+    }
+}
+
+/// The horizontal lines of a bordered table.
+enum Rule {
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// Cuts a line down to `width` columns, ending it with `…` if anything was cut.
+fn ellipsize(line: &str, width: usize) -> String {
+    if visible_width(line) <= width {
+        return line.to_string();
+    }
+    format!("{}…", truncate(line, width.saturating_sub(1)))
+}
+
+/// Renders rows of cells as a table, one string per line.
+///
+/// Columns are as wide as their widest cell, measured by visible width, so
+/// cells that are already styled line up. Rows may have different lengths;
+/// missing cells are left empty. Cells containing newlines span several lines.
+///
+/// # Arguments
+///
+/// * `rows` - The rows of cells, e.g. a `Vec<Vec<String>>` or `&[[&str; 3]]`.
+/// * `options` - How to lay out and draw the table.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{table, Border, HAlign, Style, Table, TableBorder};
+///
+/// let rows = vec![
+///     vec!["name", "size"],
+///     vec!["Cargo.toml", "741"],
+///     vec!["README.md", "12040"],
+/// ];
+///
+/// let ascii = Table::new().border(Border::Ascii).header(Style::new()).align(1, HAlign::Right);
+/// assert_eq!(
+///     table(&rows, &ascii),
+///     vec![
+///         "+------------+-------+",
+///         "| name       |  size |",
+///         "+------------+-------+",
+///         "| Cargo.toml |   741 |",
+///         "| README.md  | 12040 |",
+///         "+------------+-------+",
+///     ]
+/// );
+///
+/// let markdown = Table::new().border(TableBorder::Markdown);
+/// assert_eq!(table(&rows, &markdown)[1], "|------------|-------|");
+/// ```
+pub fn table<R, S>(rows: &[R], options: &Table) -> Vec<String>
+where
+    R: AsRef<[S]>,
+    S: AsRef<str>,
+{
+    options.render(rows)
+}
@@ -0,0 +1,241 @@
+use super::{sgr, style::Style};
+
+/// A node of a tree that [`tree`] can render.
+///
+/// Implement this for your own types to render them directly, or build a
+/// [`Node`] tree.
+pub trait TreeNode: Sized {
+    /// The text shown for this node. It may already be styled.
+    fn label(&self) -> &str;
+
+    /// The children of this node, in display order.
+    ///
+    /// Any iterator will do, so children can be kept in a `Vec`, behind
+    /// `Rc`s or `Box`es, or as a linked list of siblings.
+    fn children(&self) -> impl Iterator<Item = &Self>;
+}
+
+/// A simple tree of labels.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::Node;
+///
+/// let root = Node::new("src")
+///     .child(Node::new("lib.rs"))
+///     .child(Node::new("format").child(Node::new("mod.rs")));
+/// assert_eq!(root.children.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Node {
+    /// The text shown for this node.
+    pub label: String,
+    /// The children of this node.
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    /// Creates a node with no children.
+    pub fn new(label: impl Into<String>) -> Node {
+        Node {
+            label: label.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a child after any existing ones.
+    pub fn child(mut self, child: Node) -> Node {
+        self.children.push(child);
+        self
+    }
+}
+
+impl TreeNode for Node {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn children(&self) -> impl Iterator<Item = &Self> {
+        self.children.iter()
+    }
+}
+
+/// The strings drawn to the left of each label to connect it to its parent.
+///
+/// All four should have the same visible width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TreeGuides {
+    /// Before a child that has siblings below it, e.g. `├── `.
+    pub branch: &'static str,
+    /// Before the last child of a node, e.g. `└── `.
+    pub last: &'static str,
+    /// Below a child that has siblings below it, e.g. `│   `.
+    pub vertical: &'static str,
+    /// Below the last child of a node.
+    pub space: &'static str,
+}
+
+impl TreeGuides {
+    /// `├──`, `└──` and `│` box-drawing guides.
+    pub const UNICODE: TreeGuides = TreeGuides {
+        branch: "├── ",
+        last: "└── ",
+        vertical: "│   ",
+        space: "    ",
+    };
+
+    /// `|--` and `` `-- `` guides, for terminals without box-drawing characters.
+    pub const ASCII: TreeGuides = TreeGuides {
+        branch: "|-- ",
+        last: "`-- ",
+        vertical: "|   ",
+        space: "    ",
+    };
+}
+
+impl Default for TreeGuides {
+    fn default() -> Self {
+        TreeGuides::UNICODE
+    }
+}
+
+/// Describes how [`tree`] draws a tree: its guides, label styles and depth.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Color;
+/// use terminal_style::format::{Style, TreeStyle};
+///
+/// let options = TreeStyle::new()
+///     .guide_style(Style::new().faint())
+///     .branch_style(Style::new().bold().fg(Color::Ansi16(4)))
+///     .max_depth(2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TreeStyle {
+    guides: TreeGuides,
+    guide_style: Style,
+    branch_style: Style,
+    leaf_style: Style,
+    max_depth: Option<usize>,
+}
+
+impl TreeStyle {
+    /// Creates the default tree style: Unicode guides, no styling and no depth limit.
+    pub fn new() -> TreeStyle {
+        TreeStyle::default()
+    }
+
+    /// Sets the guide strings.
+    pub fn guides(mut self, guides: TreeGuides) -> TreeStyle {
+        self.guides = guides;
+        self
+    }
+
+    /// Sets the style the guides are drawn in.
+    pub fn guide_style(mut self, style: Style) -> TreeStyle {
+        self.guide_style = style;
+        self
+    }
+
+    /// Sets the style of labels of nodes that have children.
+    ///
+    /// Labels that are already styled keep their own styling on top of it.
+    pub fn branch_style(mut self, style: Style) -> TreeStyle {
+        self.branch_style = style;
+        self
+    }
+
+    /// Sets the style of labels of nodes without children.
+    ///
+    /// Labels that are already styled keep their own styling on top of it.
+    pub fn leaf_style(mut self, style: Style) -> TreeStyle {
+        self.leaf_style = style;
+        self
+    }
+
+    /// Only shows nodes up to `depth` levels below the root.
+    pub fn max_depth(mut self, depth: usize) -> TreeStyle {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Renders a tree. See [`tree`].
+    pub fn render<N: TreeNode>(&self, root: &N) -> Vec<String> {
+        let mut lines = Vec::new();
+        self.render_node(root, "", "", 0, &mut lines);
+        lines
+    }
+
+    /// Renders `node` and its descendants. `connector` is drawn before the
+    /// first line of the label, and `prefix` before every later line.
+    fn render_node<N: TreeNode>(
+        &self,
+        node: &N,
+        connector: &str,
+        prefix: &str,
+        depth: usize,
+        lines: &mut Vec<String>,
+    ) {
+        let mut children = node.children().peekable();
+        let base = if children.peek().is_none() {
+            self.leaf_style
+        } else {
+            self.branch_style
+        };
+        for (i, line) in node.label().split('\n').enumerate() {
+            let guide = if i == 0 { connector } else { prefix };
+            let label = sgr::restyle(line, |_, _, current| base.patch(&current));
+            if guide.is_empty() {
+                lines.push(label);
+            } else {
+                lines.push(format!("{}{}", self.guide_style.paint(guide), label));
+            }
+        }
+
+        if self.max_depth.is_some_and(|max| depth >= max) {
+            return;
+        }
+        while let Some(child) = children.next() {
+            let (connector, below) = if children.peek().is_none() {
+                (self.guides.last, self.guides.space)
+            } else {
+                (self.guides.branch, self.guides.vertical)
+            };
+            self.render_node(
+                child,
+                &format!("{}{}", prefix, connector),
+                &format!("{}{}", prefix, below),
+                depth + 1,
+                lines,
+            );
+        }
+    }
+}
+
+/// Renders a tree into lines, connecting each node to its parent with guides.
+///
+/// # Arguments
+///
+/// * `root` - The root node, drawn without a guide.
+/// * `style` - How to draw the tree.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{bold, tree, Node, TreeStyle};
+///
+/// let root = Node::new(bold("src"))
+///     .child(Node::new("format").child(Node::new("mod.rs")))
+///     .child(Node::new("lib.rs"));
+///
+/// assert_eq!(
+///     tree(&root, &TreeStyle::new()),
+///     vec!["\x1b[1msrc\x1b[0m", "├── format", "│   └── mod.rs", "└── lib.rs"]
+/// );
+/// ```
+pub fn tree<N: TreeNode>(root: &N, style: &TreeStyle) -> Vec<String> {
+    style.render(root)
+}
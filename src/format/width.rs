@@ -0,0 +1,122 @@
+use unicode_width::UnicodeWidthChar;
+
+use super::{sgr, style::Style};
+
+/// Returns the number of terminal columns a character occupies.
+///
+/// Wide characters (CJK, most emoji) take two columns; combining marks and
+/// control characters take none.
+pub(crate) fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Returns the number of terminal columns a string occupies when printed.
+///
+/// Escape sequences take up no space, and wide characters count as two
+/// columns, so this is the width to use when aligning styled text.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::format::{bold, visible_width};
+///
+/// assert_eq!(visible_width(&bold("hello")), 5);
+/// assert_eq!(visible_width("日本"), 4);
+/// ```
+pub fn visible_width(s: &str) -> usize {
+    sgr::strip(s).chars().map(char_width).sum()
+}
+
+/// Cuts a styled string down to at most `width` visible columns.
+///
+/// Escape sequences are kept, and the result ends with a reset if it is styled.
+/// A wide character that would only half fit is dropped.
+pub(crate) fn truncate(s: &str, width: usize) -> String {
+    if visible_width(s) <= width {
+        return s.to_string();
+    }
+
+    let mut used = 0;
+    let pieces = sgr::parse(s)
+        .pieces
+        .into_iter()
+        .take_while(|piece| match piece {
+            sgr::Piece::Char(c, _) => {
+                used += char_width(*c);
+                used <= width
+            }
+            sgr::Piece::Escape(_) => true,
+        });
+    sgr::render(pieces, Style::new())
+}
+
+/// Wraps a styled string into lines of at most `width` visible columns.
+///
+/// Lines are broken at spaces where possible, and words longer than `width`
+/// are split. Newlines always start a new line. Each line keeps the styling
+/// of its characters and ends with a reset if it is styled.
+pub(crate) fn wrap(s: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let pieces = sgr::parse(s).pieces;
+    let render = |range: &[sgr::Piece]| sgr::render(range.iter().copied(), Style::new());
+
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut used = 0;
+    // The last space on the current line, where it can be broken
+    let mut space: Option<usize> = None;
+    let mut i = 0;
+
+    while i < pieces.len() {
+        let c = match pieces[i] {
+            sgr::Piece::Char(c, _) => c,
+            sgr::Piece::Escape(_) => {
+                i += 1;
+                continue;
+            }
+        };
+
+        if c == '\n' {
+            lines.push(render(&pieces[start..i]));
+            (start, used, space) = (i + 1, 0, None);
+            i += 1;
+            continue;
+        }
+
+        let w = char_width(c);
+        if used > 0 && used + w > width {
+            if c == ' ' {
+                // Break at this space, dropping it
+                lines.push(render(&pieces[start..i]));
+                (start, used, space) = (i + 1, 0, None);
+                i += 1;
+            } else if let Some(at) = space {
+                // Break at the last space and carry the rest of the word over
+                lines.push(render(&pieces[start..at]));
+                start = at + 1;
+                used = pieces[start..i]
+                    .iter()
+                    .map(|piece| match piece {
+                        sgr::Piece::Char(c, _) => char_width(*c),
+                        sgr::Piece::Escape(_) => 0,
+                    })
+                    .sum();
+                space = None;
+            } else {
+                lines.push(render(&pieces[start..i]));
+                (start, used) = (i, 0);
+            }
+            // Look at the current character again on the new line
+            continue;
+        }
+
+        if c == ' ' {
+            space = Some(i);
+        }
+        used += w;
+        i += 1;
+    }
+
+    lines.push(render(&pieces[start..]));
+    lines
+}
@@ -0,0 +1,177 @@
+use std::io::{self, Write};
+
+use super::{
+    sgr::{self, Piece},
+    style::Style,
+};
+
+/// A writer that keeps track of the terminal's current style and only emits
+/// the attributes that change.
+///
+/// Writing each span with [`Style::paint`] sets every attribute at the start of
+/// the span and resets them all at the end. `StyledWriter` instead remembers
+/// the style the terminal was left in and emits the shortest transition to the
+/// next one (see [`Style::transition`]), so consecutive spans that share
+/// attributes cost little or nothing.
+///
+/// A final reset is written when the writer is dropped, or by [`reset`](Self::reset).
+///
+/// Text written through [`io::Write`] (e.g. with `write!`) is passed through
+/// unchanged and displays in the current style. Escape sequences written that
+/// way are not tracked; use [`write_styled`](Self::write_styled) for text that
+/// is already styled.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::color::Color;
+/// use terminal_style::format::{Style, StyledWriter};
+///
+/// let red = Style::new().fg(Color::Ansi16(1));
+/// let mut writer = StyledWriter::new(Vec::new());
+/// writer.write_styled(&red, "error")?;
+/// writer.write_styled(&red.bold(), ": disk full")?;
+/// let bytes = writer.into_inner()?;
+///
+/// assert_eq!(bytes, b"\x1b[31merror\x1b[1m: disk full\x1b[0m");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct StyledWriter<W: io::Write> {
+    // Only `None` once `into_inner` has taken the writer
+    inner: Option<W>,
+    // The style the terminal is in
+    current: Style,
+    // The style the next text is written in. The terminal only switches to
+    // it before that text, so a reset that follows costs no transition.
+    target: Style,
+}
+
+impl<W: io::Write> StyledWriter<W> {
+    /// Creates a writer that assumes the terminal starts out unstyled.
+    pub fn new(inner: W) -> Self {
+        StyledWriter {
+            inner: Some(inner),
+            current: Style::new(),
+            target: Style::new(),
+        }
+    }
+
+    /// Returns the style that text written next is displayed in.
+    pub fn style(&self) -> Style {
+        self.target
+    }
+
+    /// Switches the terminal to `style`, emitting only the attributes that differ.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the underlying writer.
+    pub fn set_style(&mut self, style: &Style) -> io::Result<()> {
+        self.target = *style;
+        self.sync()
+    }
+
+    /// Emits the transition from the terminal's style to the target style.
+    fn sync(&mut self) -> io::Result<()> {
+        let transition = self.current.transition(&self.target);
+        if !transition.is_empty() {
+            self.get_mut().write_all(transition.as_bytes())?;
+        }
+        self.current = self.target;
+        Ok(())
+    }
+
+    /// Writes `text` in `style`.
+    ///
+    /// Any styling already in `text` is kept, layered on top of `style` (see
+    /// [`Style::patch`]). Other escape sequences are written unchanged.
+    /// Afterwards, text is displayed in `style` again, so the next span only
+    /// needs to change what differs from it. If `text` ended in styling of its
+    /// own, the switch back to `style` waits for the next output and is
+    /// skipped if that is a reset.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the underlying writer.
+    pub fn write_styled(&mut self, style: &Style, text: &str) -> io::Result<()> {
+        if !text.contains('\x1b') {
+            self.set_style(style)?;
+            return self.get_mut().write_all(text.as_bytes());
+        }
+
+        let mut buf = [0; 4];
+        for piece in sgr::parse(text).pieces {
+            match piece {
+                Piece::Char(c, own) => {
+                    self.set_style(&style.patch(&own))?;
+                    self.get_mut()
+                        .write_all(c.encode_utf8(&mut buf).as_bytes())?;
+                }
+                Piece::Escape(sequence) => self.get_mut().write_all(sequence.as_bytes())?,
+            }
+        }
+        self.target = *style;
+        Ok(())
+    }
+
+    /// Returns the terminal to its default style.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the underlying writer.
+    pub fn reset(&mut self) -> io::Result<()> {
+        self.set_style(&Style::new())
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner
+            .as_ref()
+            .expect("writer is present until into_inner")
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Styles written directly to it are not tracked.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner
+            .as_mut()
+            .expect("writer is present until into_inner")
+    }
+
+    /// Resets the terminal style, flushes, and returns the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing the reset or flushing.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.reset()?;
+        self.flush()?;
+        Ok(self
+            .inner
+            .take()
+            .expect("writer is present until into_inner"))
+    }
+}
+
+impl<W: io::Write> io::Write for StyledWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.sync()?;
+        self.get_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.get_mut().flush()
+    }
+}
+
+impl<W: io::Write> Drop for StyledWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            // Errors can't be reported from drop; use `into_inner` to see them
+            let _ = self.reset();
+            let _ = self.get_mut().flush();
+        }
+    }
+}
@@ -1,2 +1,8 @@
 pub mod color;
+#[cfg(feature = "image-formats")]
+pub mod decode;
 pub mod format;
+mod macros;
+
+#[doc(hidden)]
+pub use macros::{__RgbInteger, __rgb_component, __rgb_integer, __style_from_spec};
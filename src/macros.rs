@@ -0,0 +1,139 @@
+//! Compile-time checked color and style macros.
+
+use crate::format::Style;
+
+/// Builds a compile-time checked `[u8; 3]` from a hex color string.
+///
+/// Accepts 3 or 6 hex digits with an optional leading `#`. Invalid input is a
+/// compile error rather than a runtime [`InvalidHex`](crate::color::ColorConversionError::InvalidHex).
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::hex;
+///
+/// const PINK: [u8; 3] = hex!("#FF1493");
+/// assert_eq!(PINK, [255, 20, 147]);
+/// assert_eq!(hex!("F0A"), [255, 0, 170]);
+/// ```
+///
+/// ```compile_fail
+/// const OOPS: [u8; 3] = terminal_style::hex!("#FF14");
+/// ```
+#[macro_export]
+macro_rules! hex {
+    ($hex:expr) => {{
+        const RGB: [u8; 3] = match $crate::color::checked_hex_to_rgb($hex) {
+            Some(rgb) => rgb,
+            None => panic!("invalid hex color: expected 3 or 6 hex digits"),
+        };
+        RGB
+    }};
+}
+
+/// Builds a compile-time checked `[u8; 3]` from red, green and blue components.
+///
+/// Each component must be an integer literal or constant from 0 to 255;
+/// anything else, including a float, is a compile error.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::rgb;
+///
+/// const PINK: [u8; 3] = rgb!(255, 20, 147);
+/// assert_eq!(PINK, [255, 20, 147]);
+/// ```
+///
+/// ```compile_fail
+/// const OOPS: [u8; 3] = terminal_style::rgb!(256, 0, 0);
+/// ```
+///
+/// Floats are rejected rather than truncated:
+///
+/// ```compile_fail
+/// const OOPS: [u8; 3] = terminal_style::rgb!(255.9, -0.5, 0);
+/// ```
+#[macro_export]
+macro_rules! rgb {
+    ($r:expr, $g:expr, $b:expr $(,)?) => {{
+        const RGB: [u8; 3] = [
+            $crate::__rgb_component($crate::__rgb_integer(&($r)), ($r) as i128),
+            $crate::__rgb_component($crate::__rgb_integer(&($g)), ($g) as i128),
+            $crate::__rgb_component($crate::__rgb_integer(&($b)), ($b) as i128),
+        ];
+        RGB
+    }};
+}
+
+/// Builds a compile-time checked [`Style`](crate::format::Style) from a spec string.
+///
+/// Uses the same syntax as [`Style::from_str`](crate::format::Style): attribute
+/// names, an optional foreground color, and an optional background color
+/// introduced by `on`. Invalid specs are a compile error.
+///
+/// # Example
+///
+/// ```
+/// use terminal_style::{color::Color, format::Style, style};
+///
+/// const HEADING: Style = style!("bold underline #FF1493 on #222");
+/// assert_eq!(
+///     HEADING,
+///     Style::new()
+///         .bold()
+///         .underline()
+///         .fg(Color::Rgb([255, 20, 147]))
+///         .bg(Color::Rgb([34, 34, 34]))
+/// );
+/// assert_eq!(style!("italic ansi:4").paint("Hi"), "\x1b[3;34mHi\x1b[0m");
+/// ```
+///
+/// ```compile_fail
+/// const OOPS: terminal_style::format::Style = terminal_style::style!("bold on");
+/// ```
+#[macro_export]
+macro_rules! style {
+    ($spec:expr) => {{
+        const STYLE: $crate::format::Style = match $crate::__style_from_spec($spec) {
+            Ok(style) => style,
+            Err(message) => panic!("{}", message),
+        };
+        STYLE
+    }};
+}
+
+/// The integer types accepted as [`rgb!`] components.
+#[doc(hidden)]
+pub trait __RgbInteger {}
+
+macro_rules! rgb_integer {
+    ($($t:ty)*) => {
+        $(impl __RgbInteger for $t {})*
+    };
+}
+
+rgb_integer!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// Fails to compile unless an [`rgb!`] component is an integer, so that
+/// floats and other castable types are not silently truncated.
+#[doc(hidden)]
+pub const fn __rgb_integer<T: __RgbInteger>(_: &T) {}
+
+/// Checks that an [`rgb!`] component fits in a `u8`.
+#[doc(hidden)]
+pub const fn __rgb_component(_: (), value: i128) -> u8 {
+    if value < 0 || value > 255 {
+        panic!("invalid RGB component: expected a value from 0 to 255");
+    }
+    value as u8
+}
+
+/// Parses a [`style!`] spec, reducing errors to a fixed message.
+#[doc(hidden)]
+pub const fn __style_from_spec(spec: &str) -> Result<Style, &'static str> {
+    match Style::parse_spec(spec.as_bytes()) {
+        Ok(style) => Ok(style),
+        Err((err, _, _)) => Err(err.message()),
+    }
+}
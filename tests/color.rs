@@ -1,7 +1,9 @@
 use terminal_style::color::{
-    ansi8_to_hex, ansi8_to_rgb, rgb_to_ansi8, rgb_to_hex, validate_ansi, validate_hex,
-    ColorConversionError,
+    ansi8_to_hex, ansi8_to_rgb, checked_hex_to_rgb, dither, dither_grid, hsl_to_rgb, rgb_to_ansi8,
+    rgb_to_hex, rgb_to_hsl, try_hex_to_ansi8, try_hex_to_rgb, validate_ansi, validate_hex, Color,
+    ColorConversionError, ColorDepth, Dither, Gradient, IntoRgb, Palette,
 };
+use terminal_style::{hex, rgb};
 
 //
 // 1. RGB ↔ Hex
@@ -131,3 +133,296 @@ fn test_validate_ansi_error_value() {
         panic!("Expected InvalidAnsiValue error");
     }
 }
+
+//
+// 7. Fallible hex conversions
+//
+#[test]
+fn test_try_hex_to_rgb_valid_inputs() {
+    assert_eq!(try_hex_to_rgb("#FF00AA"), Ok([255, 0, 170]));
+    assert_eq!(try_hex_to_rgb("f0a"), Ok([255, 0, 170]));
+}
+
+#[test]
+fn test_try_hex_to_rgb_never_panics() {
+    let cases = ["", "#", "12", "#1234", "ééé", "#€€", "##123456", "12345g"];
+
+    for input in cases {
+        assert!(
+            matches!(try_hex_to_rgb(input), Err(ColorConversionError::InvalidHex(_))),
+            "Expected '{}' to be rejected",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_try_hex_to_ansi8() {
+    assert_eq!(try_hex_to_ansi8("#FF0000"), Ok(196));
+    assert_eq!(try_hex_to_ansi8("0F0"), Ok(46));
+    assert!(try_hex_to_ansi8("#F0").is_err());
+}
+
+//
+// 8. Color enum
+//
+#[test]
+fn test_color_from_str() {
+    assert_eq!("#FF1493".parse(), Ok(Color::Rgb([255, 20, 147])));
+    assert_eq!("f0a".parse(), Ok(Color::Rgb([255, 0, 170])));
+    assert_eq!("ansi:4".parse(), Ok(Color::Ansi16(4)));
+    assert_eq!("ansi:196".parse(), Ok(Color::Ansi256(196)));
+    assert_eq!("Bright_Blue".parse(), Ok(Color::Ansi16(12)));
+    assert_eq!("grey".parse(), Ok(Color::Ansi16(8)));
+    assert_eq!("default".parse(), Ok(Color::Default));
+    // Without a `#`, words made of hex digits are colors too
+    assert_eq!("bad".parse(), Ok(Color::Rgb([187, 170, 221])));
+}
+
+#[test]
+fn test_color_from_str_errors() {
+    assert_eq!(
+        "ansi:300".parse::<Color>(),
+        Err(ColorConversionError::InvalidAnsiValue(300))
+    );
+    assert!(matches!("#12".parse::<Color>(), Err(ColorConversionError::InvalidHex(_))));
+    assert!(matches!("ansi:x".parse::<Color>(), Err(ColorConversionError::UnknownFormat(_))));
+    assert!(matches!("purple".parse::<Color>(), Err(ColorConversionError::UnknownFormat(_))));
+}
+
+#[test]
+fn test_color_display_roundtrip() {
+    let colors = [
+        Color::Default,
+        Color::Ansi16(1),
+        Color::Ansi16(15),
+        Color::Ansi16(0),
+        Color::Ansi256(0),
+        Color::Ansi256(4),
+        Color::Ansi256(15),
+        Color::Ansi256(16),
+        Color::Ansi256(255),
+        Color::Rgb([18, 52, 86]),
+    ];
+
+    for color in colors {
+        assert_eq!(color.to_string().parse(), Ok(color), "Roundtrip of {}", color);
+    }
+    assert_eq!(Color::Ansi16(13).to_string(), "bright-magenta");
+    assert_eq!(Color::Ansi256(4).to_string(), "ansi256:4");
+    assert_eq!("ansi256:4".parse::<Color>().unwrap().fg_sequence(), "\x1b[38;5;4m");
+    assert_eq!("ansi256:200".parse(), Ok(Color::Ansi256(200)));
+    assert_eq!("ansi256:256".parse::<Color>(), Err(ColorConversionError::InvalidAnsiValue(256)));
+
+    // An Ansi16 outside the 16 colors comes back as the palette index it emits
+    let out_of_range = Color::Ansi16(200);
+    let parsed: Color = out_of_range.to_string().parse().unwrap();
+    assert_eq!(parsed, Color::Ansi256(200));
+    assert_eq!(parsed.fg_sequence(), out_of_range.fg_sequence());
+}
+
+#[test]
+fn test_color_sequences_respect_kind() {
+    assert_eq!(Color::Ansi16(4).fg_sequence(), "\x1b[34m");
+    assert_eq!(Color::Ansi16(12).fg_sequence(), "\x1b[94m");
+    assert_eq!(Color::Ansi16(1).bg_sequence(), "\x1b[41m");
+    assert_eq!(Color::Ansi256(4).fg_sequence(), "\x1b[38;5;4m");
+    assert_eq!(Color::Rgb([1, 2, 3]).bg_sequence(), "\x1b[48;2;1;2;3m");
+    assert_eq!(Color::Default.bg_sequence(), "\x1b[49m");
+}
+
+#[test]
+fn test_color_into_rgb() {
+    assert_eq!(Color::Ansi256(196).into_rgb(), Ok([255, 0, 0]));
+    assert_eq!(Color::Rgb([1, 2, 3]).into_rgb(), Ok([1, 2, 3]));
+    assert!(Color::Default.into_rgb().is_err());
+}
+
+//
+// 9. Compile-time checked colors
+//
+const PALETTE: [[u8; 3]; 3] = [hex!("#FF1493"), hex!("0f0"), rgb!(34, 34, 34)];
+
+#[test]
+fn test_color_macros() {
+    assert_eq!(PALETTE, [[255, 20, 147], [0, 255, 0], [34, 34, 34]]);
+    assert_eq!(rgb!(0, 128, 255,), [0, 128, 255]);
+    const LEVEL: u8 = 34;
+    assert_eq!(rgb!(LEVEL, 255u16, 0i64), [34, 255, 0]);
+}
+
+#[test]
+fn test_checked_hex_to_rgb_matches_validate_hex() {
+    let inputs = ["#fff", "#FFFFFF", "abc", "123456", "", "#", "#12", "#abcd", "##123456", "ééé"];
+
+    for input in inputs {
+        assert_eq!(
+            checked_hex_to_rgb(input).is_some(),
+            validate_hex(input).is_ok(),
+            "Disagreement on '{}'",
+            input
+        );
+    }
+    assert_eq!(checked_hex_to_rgb("#AbC"), Some([170, 187, 204]));
+}
+
+//
+// 10. HSL conversions
+//
+#[test]
+fn test_hsl_to_rgb_primaries() {
+    assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), [255, 0, 0]);
+    assert_eq!(hsl_to_rgb(60.0, 1.0, 0.5), [255, 255, 0]);
+    assert_eq!(hsl_to_rgb(120.0, 1.0, 0.5), [0, 255, 0]);
+    assert_eq!(hsl_to_rgb(180.0, 1.0, 0.5), [0, 255, 255]);
+    assert_eq!(hsl_to_rgb(300.0, 1.0, 0.5), [255, 0, 255]);
+    assert_eq!(hsl_to_rgb(-60.0, 1.0, 0.5), [255, 0, 255]);
+    assert_eq!(hsl_to_rgb(200.0, 0.0, 0.5), [128, 128, 128]);
+    assert_eq!(hsl_to_rgb(200.0, 1.0, 0.0), [0, 0, 0]);
+    assert_eq!(hsl_to_rgb(200.0, 1.0, 2.0), [255, 255, 255]);
+}
+
+#[test]
+fn test_hsl_roundtrip() {
+    let colors = [[255, 20, 147], [34, 34, 34], [0, 128, 255], [130, 87, 173]];
+
+    for rgb in colors {
+        let (h, s, l) = rgb_to_hsl(rgb);
+        assert_eq!(hsl_to_rgb(h, s, l), rgb, "Roundtrip of {:?}", rgb);
+    }
+}
+
+//
+// 11. Gradients
+//
+#[test]
+fn test_gradient_at() {
+    let gradient = Gradient::new(["#000000", "#FFFFFF", "#FF0000"]).unwrap();
+    assert_eq!(gradient.at(0.0), [0, 0, 0]);
+    assert_eq!(gradient.at(0.25), [128, 128, 128]);
+    assert_eq!(gradient.at(0.5), [255, 255, 255]);
+    assert_eq!(gradient.at(1.0), [255, 0, 0]);
+    assert_eq!(gradient.at(-1.0), [0, 0, 0]);
+    assert_eq!(gradient.at(7.0), [255, 0, 0]);
+
+    let solid = Gradient::from([1, 2, 3]);
+    assert_eq!(solid.at(0.7), [1, 2, 3]);
+}
+
+#[test]
+fn test_gradient_errors() {
+    assert!(matches!(
+        Gradient::new(Vec::<&str>::new()),
+        Err(ColorConversionError::InvalidRgb(_))
+    ));
+    assert!(matches!(
+        Gradient::new(["#FF0000", "nope"]),
+        Err(ColorConversionError::InvalidHex(_))
+    ));
+}
+
+#[test]
+fn test_color_depth() {
+    assert_eq!(ColorDepth::TrueColor.color([1, 2, 3]), Color::Rgb([1, 2, 3]));
+    assert_eq!(ColorDepth::Ansi256.color([0, 0, 0]), Color::Ansi256(16));
+}
+
+//
+// 12. Palettes and dithering
+//
+#[test]
+fn test_palettes() {
+    let xterm = Palette::xterm256();
+    assert_eq!(xterm.len(), 240);
+    assert_eq!(xterm.get(16), Some([0, 0, 0]));
+    assert_eq!(xterm.get(3), None);
+    assert_eq!(Palette::ansi16().len(), 16);
+
+    // Nearest matches agree with rgb_to_ansi8 on the palette's own colors
+    for index in [16u8, 21, 46, 196, 231, 244] {
+        let rgb = ansi8_to_rgb(index);
+        assert_eq!(xterm.nearest(rgb), rgb_to_ansi8(rgb));
+    }
+
+    assert!(matches!(
+        Palette::new(Vec::<[u8; 3]>::new()),
+        Err(ColorConversionError::InvalidRgb(_))
+    ));
+    assert!(Palette::new(vec![[0u8, 0, 0]; 257]).is_err());
+    assert_eq!(Palette::new(vec![[0u8, 0, 0]; 256]).unwrap().len(), 256);
+}
+
+#[test]
+fn test_dither_none_is_nearest() {
+    let palette = Palette::new(["#000000", "#FF0000", "#FFFFFF"]).unwrap();
+    let pixels = [10, 0, 0, 240, 20, 20, 250, 250, 250, 90, 90, 90];
+    let indices = dither(2, 2, &pixels, &palette, Dither::None).unwrap();
+    let expected: Vec<u8> = pixels
+        .chunks(3)
+        .map(|p| palette.nearest([p[0], p[1], p[2]]))
+        .collect();
+    assert_eq!(indices, expected);
+    assert_eq!(&indices[..3], &[0, 1, 2]);
+}
+
+#[test]
+fn test_dither_preserves_brightness() {
+    // Error diffusion in linear light keeps the average brightness of a
+    // flat area that falls between two palette colors
+    let mono = Palette::new(["#000000", "#FFFFFF"]).unwrap();
+    let gray = 128u8;
+    let linear = ((gray as f64 / 255.0 + 0.055) / 1.055).powf(2.4);
+    for method in [Dither::FloydSteinberg, Dither::Bayer] {
+        let indices = dither(16, 16, &[gray; 16 * 16 * 3], &mono, method).unwrap();
+        let white = indices.iter().filter(|&&i| i == 1).count() as f64 / 256.0;
+        assert!(
+            (white - linear).abs() < 0.08,
+            "{:?} lit {} of pixels, expected about {}",
+            method,
+            white,
+            linear
+        );
+    }
+
+    // Atkinson drops a quarter of the error, so midtones come out darker
+    let indices = dither(16, 16, &[gray; 16 * 16 * 3], &mono, Dither::Atkinson).unwrap();
+    let white = indices.iter().filter(|&&i| i == 1).count() as f64 / 256.0;
+    assert!(white > 0.0 && white < linear);
+
+    let plain = dither(16, 16, &[gray; 16 * 16 * 3], &mono, Dither::None).unwrap();
+    assert!(plain.iter().all(|&i| i == plain[0]));
+}
+
+#[test]
+fn test_dither_grid() {
+    let rows = vec![vec![[0u8, 0, 0], [255, 255, 255]], vec![[255, 255, 255]]];
+    let indices = dither_grid(&rows, &Palette::xterm256(), Dither::FloydSteinberg);
+    assert_eq!(indices, vec![vec![16, 231], vec![231]]);
+
+    // A smooth ramp uses more palette colors when dithered
+    let ramp: Vec<[u8; 3]> = (0..64).map(|i| [i as u8, i as u8 * 2, 120]).collect();
+    let grid = vec![ramp; 8];
+    let count = |method| {
+        let mut used: Vec<u8> = dither_grid(&grid, &Palette::xterm256(), method).concat();
+        used.sort();
+        used.dedup();
+        used.len()
+    };
+    assert!(count(Dither::FloydSteinberg) > count(Dither::None));
+    assert!(count(Dither::Bayer) > count(Dither::None));
+}
+
+#[test]
+fn test_dither_errors() {
+    assert_eq!(
+        dither(2, 1, &[0; 5], &Palette::ansi16(), Dither::None),
+        Err(ColorConversionError::InvalidRgb(
+            "expected 6 bytes for a 2x1 image, found 5".to_string()
+        ))
+    );
+    assert_eq!(dither(0, 3, &[], &Palette::ansi16(), Dither::Atkinson), Ok(vec![]));
+    assert!(matches!(
+        dither(usize::MAX, 2, &[], &Palette::ansi16(), Dither::None),
+        Err(ColorConversionError::InvalidRgb(_))
+    ));
+}
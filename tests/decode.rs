@@ -0,0 +1,226 @@
+#![cfg(feature = "image-formats")]
+
+use terminal_style::decode::{bmp, decode, farbfeld, pnm, DecodeError, DecodedImage};
+use terminal_style::format::image::{self, ImageData};
+use terminal_style::format::sixel;
+
+/// Builds a bitmap file with a 40-byte header, followed by `extra` header
+/// bytes (e.g. channel masks) and the pixel rows.
+fn bitmap(width: i32, height: i32, bits: u16, compression: u32, extra: &[u8], rows: &[u8]) -> Vec<u8> {
+    let offset = 54 + extra.len() as u32;
+    let mut file = b"BM".to_vec();
+    file.extend((offset + rows.len() as u32).to_le_bytes());
+    file.extend([0; 4]);
+    file.extend(offset.to_le_bytes());
+    file.extend(40u32.to_le_bytes());
+    file.extend(width.to_le_bytes());
+    file.extend(height.to_le_bytes());
+    file.extend(1u16.to_le_bytes());
+    file.extend(bits.to_le_bytes());
+    file.extend(compression.to_le_bytes());
+    file.extend([0; 20]);
+    file.extend_from_slice(extra);
+    file.extend_from_slice(rows);
+    file
+}
+
+#[test]
+fn test_pnm_binary_pixmap() {
+    let image = pnm::decode(b"P6\n2 2\n255\n\xFF\x00\x00\x00\xFF\x00\x00\x00\xFF\xFF\xFF\xFF").unwrap();
+    assert_eq!(
+        image,
+        DecodedImage {
+            width: 2,
+            height: 2,
+            alpha: false,
+            pixels: vec![255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255],
+        }
+    );
+    assert_eq!(image.channels(), 3);
+}
+
+#[test]
+fn test_pnm_ascii_with_comments_and_scaling() {
+    let image = pnm::decode(b"P3 # made by hand\n# size\n2 1\n15\n15 0 0 # red\n 0 0 7\n").unwrap();
+    assert_eq!(image.pixels, vec![255, 0, 0, 0, 0, 119]);
+
+    let image = pnm::decode(b"P2 3 1 4 0 2 4").unwrap();
+    assert_eq!(image.pixels, vec![0, 0, 0, 128, 128, 128, 255, 255, 255]);
+}
+
+#[test]
+fn test_pnm_sixteen_bit_samples() {
+    let image = pnm::decode(b"P5 2 1 65535\n\xFF\xFF\x80\x00").unwrap();
+    assert_eq!(image.pixels, vec![255, 255, 255, 128, 128, 128]);
+}
+
+#[test]
+fn test_pnm_bitmaps() {
+    // 1 is black, and ASCII bits need no separators
+    let ascii = pnm::decode(b"P1\n3 2\n010\n1 0 1").unwrap();
+    let binary = pnm::decode(b"P4\n3 2\n\x40\xA0").unwrap();
+    assert_eq!(ascii, binary);
+    assert_eq!(
+        ascii.pixels.chunks(3).map(|p| p[0]).collect::<Vec<_>>(),
+        [255, 0, 255, 0, 255, 0]
+    );
+}
+
+#[test]
+fn test_pam_with_alpha() {
+    let rgba = pnm::decode(
+        b"P7\nWIDTH 2\nHEIGHT 1\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n\x01\x02\x03\x04\x05\x06\x07\x08",
+    )
+    .unwrap();
+    assert!(rgba.alpha);
+    assert_eq!(rgba.pixels, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+    let gray = pnm::decode(b"P7\n# comment\nWIDTH 1\nHEIGHT 1\nDEPTH 2\nMAXVAL 255\nTUPLTYPE GRAYSCALE_ALPHA\nENDHDR\n\x40\x80").unwrap();
+    assert_eq!(gray.channels(), 4);
+    assert_eq!(gray.pixels, vec![64, 64, 64, 128]);
+}
+
+#[test]
+fn test_pnm_errors() {
+    assert_eq!(pnm::decode(b"P6\n2 2\n255\n\xFF\x00\x00"), Err(DecodeError::Truncated));
+    assert_eq!(pnm::decode(b"P3\n1 1\n255\n0 0"), Err(DecodeError::Truncated));
+    assert_eq!(pnm::decode(b"P6\n2 2"), Err(DecodeError::Truncated));
+    assert_eq!(pnm::decode(b"P9\n"), Err(DecodeError::UnknownFormat));
+    assert!(matches!(pnm::decode(b"P6\nwide 2\n255\n"), Err(DecodeError::Invalid(_))));
+    assert!(matches!(pnm::decode(b"P2 1 1 10 11"), Err(DecodeError::Invalid(_))));
+    assert!(matches!(pnm::decode(b"P5 1 1 0\n\x00"), Err(DecodeError::Invalid(_))));
+    assert!(matches!(pnm::decode(b"P1 1 1 2"), Err(DecodeError::Invalid(_))));
+    assert!(matches!(
+        pnm::decode(b"P7\nWIDTH 1\nHEIGHT 1\nMAXVAL 255\nENDHDR\n\x00"),
+        Err(DecodeError::Invalid(_))
+    ));
+    assert_eq!(
+        pnm::decode(b"P7\nWIDTH 1\nHEIGHT 1\nDEPTH 5\nMAXVAL 255\nENDHDR\n\x00\x00\x00\x00\x00"),
+        Err(DecodeError::Unsupported("PAM depth 5".to_string()))
+    );
+    // A huge size is refused before anything is allocated
+    assert_eq!(pnm::decode(b"P3 100000 100000 255 0"), Err(DecodeError::Truncated));
+}
+
+#[test]
+fn test_bmp_bottom_up_with_padding() {
+    // Three pixels per row take nine bytes, padded to twelve; the bottom row comes first
+    let rows = [
+        0, 0, 255, 0, 255, 0, 255, 0, 0, 0, 0, 0, //
+        255, 255, 255, 0, 0, 0, 128, 128, 128, 0, 0, 0,
+    ];
+    let image = bmp::decode(&bitmap(3, 2, 24, 0, &[], &rows)).unwrap();
+    assert_eq!((image.width, image.height, image.alpha), (3, 2, false));
+    assert_eq!(
+        image.pixels,
+        vec![255, 255, 255, 0, 0, 0, 128, 128, 128, 255, 0, 0, 0, 255, 0, 0, 0, 255]
+    );
+}
+
+#[test]
+fn test_bmp_32_bit() {
+    // Top-down, with alpha in the fourth byte
+    let rows = [0, 0, 255, 128, 255, 0, 0, 255];
+    let image = bmp::decode(&bitmap(1, -2, 32, 0, &[], &rows)).unwrap();
+    assert!(image.alpha);
+    assert_eq!(image.pixels, vec![255, 0, 0, 128, 0, 0, 255, 255]);
+
+    // An unused fourth byte means no alpha
+    let rows = [0, 0, 255, 0, 255, 0, 0, 0];
+    let image = bmp::decode(&bitmap(1, -2, 32, 0, &[], &rows)).unwrap();
+    assert!(!image.alpha);
+    assert_eq!(image.pixels, vec![255, 0, 0, 0, 0, 255]);
+}
+
+#[test]
+fn test_bmp_bitfields() {
+    // Red, green and blue masks for 0x00RRGGBB pixels, with no alpha mask
+    let masks: Vec<u8> = [0x00FF_0000u32, 0x0000_FF00, 0x0000_00FF].iter().flat_map(|m| m.to_le_bytes()).collect();
+    let image = bmp::decode(&bitmap(1, 1, 32, 3, &masks, &0x0012_3456u32.to_le_bytes())).unwrap();
+    assert!(!image.alpha);
+    assert_eq!(image.pixels, vec![0x12, 0x34, 0x56]);
+
+    // Alpha bitfields, with 10-bit channels for red, green and blue
+    let masks: Vec<u8> = [0x3FF0_0000u32, 0x000F_FC00, 0x0000_03FF, 0xC000_0000]
+        .iter()
+        .flat_map(|m| m.to_le_bytes())
+        .collect();
+    let pixel = (3u32 << 30) | (1023 << 20) | 1023;
+    let image = bmp::decode(&bitmap(1, 1, 32, 6, &masks, &pixel.to_le_bytes())).unwrap();
+    assert_eq!(image.pixels, vec![255, 0, 255, 255]);
+}
+
+#[test]
+fn test_bmp_errors() {
+    let rows = [0; 8];
+    assert_eq!(bmp::decode(&bitmap(2, 2, 24, 0, &[], &rows)), Err(DecodeError::Truncated));
+    assert_eq!(bmp::decode(b"BM\x00\x00"), Err(DecodeError::Truncated));
+    assert_eq!(bmp::decode(b"MB"), Err(DecodeError::UnknownFormat));
+    assert_eq!(
+        bmp::decode(&bitmap(1, 1, 8, 0, &[], &rows)),
+        Err(DecodeError::Unsupported("8-bit bitmaps".to_string()))
+    );
+    assert!(matches!(bmp::decode(&bitmap(1, 1, 24, 1, &[], &rows)), Err(DecodeError::Unsupported(_))));
+    assert!(matches!(bmp::decode(&bitmap(-1, 1, 24, 0, &[], &rows)), Err(DecodeError::Invalid(_))));
+}
+
+#[test]
+fn test_farbfeld() {
+    let mut file = b"farbfeld".to_vec();
+    file.extend(2u32.to_be_bytes());
+    file.extend(1u32.to_be_bytes());
+    file.extend([0xFF, 0xFF, 0, 0, 0, 0, 0xFF, 0xFF, 0, 0, 0x01, 0x00, 0xFF, 0xFF, 0, 0]);
+    let image = farbfeld::decode(&file).unwrap();
+    assert_eq!((image.width, image.height, image.alpha), (2, 1, true));
+    assert_eq!(image.pixels, vec![255, 0, 0, 255, 0, 1, 255, 0]);
+
+    file.pop();
+    assert_eq!(farbfeld::decode(&file), Err(DecodeError::Truncated));
+    assert_eq!(farbfeld::decode(b"farbfel"), Err(DecodeError::UnknownFormat));
+    assert_eq!(farbfeld::decode(b"farbfeld\x00\x00"), Err(DecodeError::Truncated));
+}
+
+#[test]
+fn test_decode_detects_format() {
+    let ppm = decode(b"P6 1 1 255\n\x01\x02\x03").unwrap();
+    let bmp = decode(&bitmap(1, 1, 24, 0, &[], &[3, 2, 1, 0])).unwrap();
+    let mut farbfeld = b"farbfeld".to_vec();
+    farbfeld.extend([0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 2, 2, 3, 3, 0xFF, 0xFF]);
+    let farbfeld = decode(&farbfeld).unwrap();
+
+    assert_eq!(ppm.pixels, bmp.pixels);
+    assert_eq!(farbfeld.to_rgb([0, 0, 0]), ppm.pixels);
+    assert_eq!(decode(b"\x89PNG"), Err(DecodeError::UnknownFormat));
+    assert_eq!(decode(b""), Err(DecodeError::UnknownFormat));
+}
+
+#[test]
+fn test_decoded_image_renders() {
+    let image = decode(b"P3 1 2 255 255 0 0 0 0 255").unwrap();
+    assert_eq!(
+        image::render(image.width, image.height, &image.pixels, 1).unwrap(),
+        vec!["\x1b[38;2;255;0;0;48;2;0;0;255m▀\x1b[0m"]
+    );
+    assert_eq!(
+        sixel::encode(image.width, image.height, &image.to_rgb([0, 0, 0])).unwrap(),
+        sixel::encode(1, 2, &[255, 0, 0, 0, 0, 255]).unwrap()
+    );
+    assert_eq!(
+        image.as_image_data(),
+        ImageData::Rgb { width: 1, height: 2, pixels: &[255, 0, 0, 0, 0, 255] }
+    );
+}
+
+#[test]
+fn test_decode_error_display() {
+    assert_eq!(DecodeError::UnknownFormat.to_string(), "Unknown image format");
+    assert_eq!(DecodeError::Truncated.to_string(), "Image data ends early");
+    assert_eq!(
+        DecodeError::Invalid("bad".to_string()).to_string(),
+        "Invalid image: bad"
+    );
+    assert_eq!(
+        DecodeError::Unsupported("8-bit bitmaps".to_string()).to_string(),
+        "Unsupported image: 8-bit bitmaps"
+    );
+}
@@ -1,6 +1,24 @@
+use std::{
+    borrow::Cow,
+    io::Write,
+    time::Duration,
+    collections::{BTreeMap, HashMap, VecDeque},
+    rc::Rc,
+    sync::Arc,
+};
+use terminal_style::color::{Color, ColorConversionError, ColorDepth, Dither, Gradient, Palette};
+use terminal_style::style;
+use terminal_style::format::image::{self, ImageData, ImageOptions};
+use terminal_style::format::iterm2::{self, ITermImage};
+use terminal_style::format::kitty::{self, KittyImage};
+use terminal_style::format::sixel::{self, SixelOptions};
 use terminal_style::format::{
-    background, background_ansi, background_rgb, bold, color, color_ansi, color_rgb, faint, inverse,
-    italic, underline,
+    background, background_ansi, background_native, background_rgb, bar_chart, bold, boxed, color,
+    color_ansi, color_native, color_rgb, faint, highlight, hue_cycle, inverse, italic,
+    join_horizontal, join_vertical, per_char, place, rainbow, redraw, render_grid, sparkline, stripe_rows,
+    style_column, style_range, style_ranges, style_row, table, tree, underline, visible_width, BarChart, Border,
+    BorderChars, BoxStyle, Canvas, CanvasMode, ColorCycle, Frames, HAlign, Node, Overflow, ProgressBar, Spacing, Sparkline, Spinner, StylableIndexed, Style, StyledIterator,
+    StyledWriter, Table, TableBorder, TextRange, Ticker, TreeGuides, TreeNode, TreeStyle, VAlign,
 };
 
 #[test]
@@ -27,6 +45,23 @@ fn test_background_rgb() {
     assert_eq!(styled, "\x1b[48;2;0;0;255mBlue\x1b[0m");
 }
 
+#[test]
+fn test_color_native() {
+    assert_eq!(color_native("ansi:4", "Blue").unwrap(), "\x1b[34mBlue\x1b[0m");
+    assert_eq!(color_native(196u8, "Red").unwrap(), "\x1b[38;5;196mRed\x1b[0m");
+    assert_eq!(
+        color_native(Color::Rgb([255, 0, 0]), "Red").unwrap(),
+        "\x1b[38;2;255;0;0mRed\x1b[0m"
+    );
+    assert!(color_native("ansi:256", "Oops").is_err());
+}
+
+#[test]
+fn test_background_native() {
+    assert_eq!(background_native("green", "Go").unwrap(), "\x1b[42mGo\x1b[0m");
+    assert_eq!(background_native(Color::Default, "Plain").unwrap(), "\x1b[49mPlain\x1b[0m");
+}
+
 #[test]
 fn test_background_with_rgb_array() {
     let styled = background([0, 0, 255], "Blue").unwrap();
@@ -480,3 +515,1532 @@ fn test_2d_vector_background() {
         .collect();
     assert_eq!(bg_colored_2d, expected_bg_2d);
 }
+
+#[test]
+fn test_style_builder_paint() {
+    let style = Style::new().bold().underline().fg(Color::Rgb([255, 20, 147]));
+    assert_eq!(style.paint("Hi"), "\x1b[1;4;38;2;255;20;147mHi\x1b[0m");
+    assert_eq!(Style::new().paint("Plain"), "Plain");
+}
+
+#[test]
+fn test_style_apply_2d_vector() {
+    let texts_2d = vec![vec!["A".to_string()], vec!["B".to_string()]];
+    let styled = Style::new().bg(Color::Ansi16(1)).apply(&texts_2d);
+    assert_eq!(
+        styled,
+        vec![vec!["\x1b[41mA\x1b[0m"], vec!["\x1b[41mB\x1b[0m"]]
+    );
+}
+
+#[test]
+fn test_style_from_str() {
+    let style: Style = "bold underline #FF1493 on #222".parse().unwrap();
+    assert_eq!(
+        style,
+        Style::new()
+            .bold()
+            .underline()
+            .fg(Color::Rgb([255, 20, 147]))
+            .bg(Color::Rgb([34, 34, 34]))
+    );
+    assert_eq!("dim reverse".parse(), Ok(Style::new().faint().inverse()));
+    assert_eq!("".parse(), Ok(Style::new()));
+    assert!("bold on".parse::<Style>().is_err());
+    assert!("bold #12".parse::<Style>().is_err());
+}
+
+#[test]
+fn test_style_display_roundtrip() {
+    let style = Style::new()
+        .italic()
+        .strikethrough()
+        .fg(Color::Ansi16(2))
+        .bg(Color::Ansi256(236));
+    assert_eq!(style.to_string(), "italic strikethrough green on ansi:236");
+    assert_eq!(style.to_string().parse(), Ok(style));
+    assert_eq!(Style::new().to_string().parse(), Ok(Style::new()));
+}
+
+#[test]
+fn test_style_macro_matches_from_str() {
+    const HEADING: Style = style!("bold underline #FF1493 on #222");
+    assert_eq!(Ok(HEADING), "bold underline #FF1493 on #222".parse());
+    assert_eq!(
+        style!("dim reverse grey on ansi:17"),
+        "faint inverse bright-black on ansi:17".parse().unwrap()
+    );
+    assert_eq!(style!(""), Style::new());
+}
+
+#[test]
+fn test_style_from_str_error_details() {
+    assert_eq!(
+        "bold #12".parse::<Style>(),
+        Err(ColorConversionError::InvalidHex(
+            "Expected 3 or 6-digit hex string, got: #12".to_string()
+        ))
+    );
+    assert_eq!(
+        "on ansi:300".parse::<Style>(),
+        Err(ColorConversionError::InvalidAnsiValue(300))
+    );
+    assert_eq!(
+        "bold sparkly".parse::<Style>(),
+        Err(ColorConversionError::UnknownFormat("sparkly".to_string()))
+    );
+    // Only one color of each kind
+    assert_eq!(
+        "red blue".parse::<Style>(),
+        Err(ColorConversionError::UnknownFormat(
+            "More than one foreground color in: red blue".to_string()
+        ))
+    );
+    assert_eq!(
+        "on red bold on #222".parse::<Style>(),
+        Err(ColorConversionError::UnknownFormat(
+            "More than one background color in: on red bold on #222".to_string()
+        ))
+    );
+    // Bare hex digits are not colors here, so misspelled words are errors
+    for word in ["bad", "add", "face", "FF1493"] {
+        assert_eq!(
+            format!("bold {}", word).parse::<Style>(),
+            Err(ColorConversionError::UnknownFormat(word.to_string()))
+        );
+    }
+    assert_eq!(
+        "on bad".parse::<Style>(),
+        Err(ColorConversionError::UnknownFormat("bad".to_string()))
+    );
+}
+
+#[test]
+fn test_bold_vec_of_str() {
+    let styled: Vec<String> = bold(vec!["a", "b"]);
+    assert_eq!(styled, vec!["\x1b[1ma\x1b[0m", "\x1b[1mb\x1b[0m"]);
+}
+
+#[test]
+fn test_bold_slice_and_array() {
+    let words = ["a", "b"];
+    let from_slice: Vec<String> = bold(&words[..]);
+    let from_array: [String; 2] = bold(words);
+    assert_eq!(from_slice, from_array.to_vec());
+    assert_eq!(from_array, ["\x1b[1ma\x1b[0m", "\x1b[1mb\x1b[0m"]);
+}
+
+#[test]
+fn test_color_2d_vec_of_str() {
+    let grid = vec![vec!["A", "B"], vec!["C"]];
+    let styled: Vec<Vec<String>> = color_ansi(196u8, &grid).unwrap();
+    assert_eq!(
+        styled,
+        vec![
+            vec!["\x1b[38;5;196mA\x1b[0m", "\x1b[38;5;196mB\x1b[0m"],
+            vec!["\x1b[38;5;196mC\x1b[0m"],
+        ]
+    );
+}
+
+#[test]
+fn test_bold_owned_and_shared_strings() {
+    let expected = "\x1b[1mHi\x1b[0m";
+    assert_eq!(bold(Cow::Borrowed("Hi")), expected);
+    assert_eq!(bold(Cow::<str>::Owned("Hi".to_string())), expected);
+    assert_eq!(bold(Box::<str>::from("Hi")), expected);
+    assert_eq!(bold(Rc::<str>::from("Hi")), expected);
+    assert_eq!(bold(Arc::<str>::from("Hi")), expected);
+}
+
+#[test]
+fn test_bold_option() {
+    assert_eq!(bold(Some("Hi")), Some("\x1b[1mHi\x1b[0m".to_string()));
+    assert_eq!(bold(None::<&str>), None);
+}
+
+#[test]
+fn test_color_array_propagates_errors() {
+    assert!(color("#nope", ["a", "b"]).is_err());
+    assert_eq!(
+        color([0, 0, 255], [Some("a"), None]).unwrap(),
+        [Some("\x1b[38;2;0;0;255ma\x1b[0m".to_string()), None]
+    );
+}
+
+#[test]
+fn test_bold_map_values_keep_keys() {
+    let mut row = HashMap::new();
+    row.insert("name", "api".to_string());
+    row.insert("status", "ok".to_string());
+
+    let styled: HashMap<&str, String> = bold(&row);
+    assert_eq!(styled["name"], "\x1b[1mapi\x1b[0m");
+    assert_eq!(styled["status"], "\x1b[1mok\x1b[0m");
+}
+
+#[test]
+fn test_color_btreemap_of_rows() {
+    let mut report = BTreeMap::new();
+    report.insert(2, vec!["b"]);
+    report.insert(1, vec!["a"]);
+
+    let styled = color_ansi(21u8, report).unwrap();
+    assert_eq!(
+        styled.into_iter().collect::<Vec<_>>(),
+        vec![
+            (1, vec!["\x1b[38;5;21ma\x1b[0m".to_string()]),
+            (2, vec!["\x1b[38;5;21mb\x1b[0m".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn test_italic_vecdeque() {
+    let lines: VecDeque<String> = VecDeque::from(vec!["x".to_string(), "y".to_string()]);
+    let styled = italic(&lines);
+    assert_eq!(styled, vec!["\x1b[3mx\x1b[0m", "\x1b[3my\x1b[0m"]);
+}
+
+#[test]
+fn test_styled_iterator_paints_any_stylable_item() {
+    let underline = Style::new().underline();
+    let produced = std::cell::Cell::new(0);
+    let mut lines = (0..3)
+        .map(|n| {
+            produced.set(produced.get() + 1);
+            n.to_string()
+        })
+        .styled(underline);
+
+    assert_eq!(produced.get(), 0);
+    assert_eq!(lines.next(), Some("\x1b[4m0\x1b[0m".to_string()));
+    assert_eq!(produced.get(), 1);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines.next_back(), Some("\x1b[4m2\x1b[0m".to_string()));
+
+    let words: Vec<String> = ["a", "b"].into_iter().styled(underline).collect();
+    assert_eq!(words, vec!["\x1b[4ma\x1b[0m", "\x1b[4mb\x1b[0m"]);
+    let rows: Vec<Vec<String>> = vec![vec!["x", "y"]].into_iter().styled(underline).collect();
+    assert_eq!(rows, vec![vec!["\x1b[4mx\x1b[0m", "\x1b[4my\x1b[0m"]]);
+    let plain: Vec<String> = ["z"].iter().styled(Style::new()).collect();
+    assert_eq!(plain, vec!["z"]);
+}
+
+#[test]
+fn test_apply_indexed_flat() {
+    let lines = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    let numbered = lines.apply_indexed(|i, s| format!("{}:{}", i, s));
+    assert_eq!(numbered, vec!["0:a", "1:b", "2:c"]);
+}
+
+#[test]
+fn test_apply_indexed_checkerboard() {
+    let grid = vec![vec![" "; 3]; 2];
+    let board = grid.apply_indexed(|(row, col), s| {
+        if (row + col) % 2 == 0 {
+            inverse(s)
+        } else {
+            s.to_string()
+        }
+    });
+    assert_eq!(
+        board,
+        vec![
+            vec!["\x1b[7m \x1b[0m", " ", "\x1b[7m \x1b[0m"],
+            vec![" ", "\x1b[7m \x1b[0m", " "],
+        ]
+    );
+}
+
+#[test]
+fn test_apply_indexed_generic_elements() {
+    let number = |i: usize, s: &str| format!("{}:{}", i, s);
+    let cows: Vec<Cow<str>> = vec![Cow::Borrowed("a"), Cow::Owned("b".to_string())];
+    assert_eq!(cows.apply_indexed(number), ["0:a", "1:b"]);
+    let boxed: Vec<Box<str>> = vec!["a".into(), "b".into()];
+    assert_eq!(boxed.apply_indexed(number), ["0:a", "1:b"]);
+    let (a, b) = ("a".to_string(), "b".to_string());
+    assert_eq!(vec![&a, &b].apply_indexed(number), ["0:a", "1:b"]);
+    assert_eq!(["a", "b"].apply_indexed(number), ["0:a", "1:b"]);
+    let shared: [Rc<str>; 1] = ["a".into()];
+    assert_eq!(shared.apply_indexed(number), ["0:a"]);
+
+    let cell = |(row, col): (usize, usize), s: &str| format!("{}{}{}", row, col, s);
+    assert_eq!([["a", "b"], ["c", "d"]].apply_indexed(cell), [["00a", "01b"], ["10c", "11d"]]);
+    let row: &[Box<str>] = &["x".into(), "y".into()];
+    assert_eq!(vec![row, row].apply_indexed(cell), [["00x", "01y"], ["10x", "11y"]]);
+    let arcs: Vec<Arc<[Cow<str>]>> = vec![Arc::from(vec![Cow::Borrowed("q")])];
+    assert_eq!(arcs.apply_indexed(cell), [["00q"]]);
+
+    let striped = stripe_rows(Style::new(), Style::new().bold(), [["a"], ["b"]]);
+    assert_eq!(striped, [["a"], ["\x1b[1mb\x1b[0m"]]);
+}
+
+#[test]
+fn test_apply_indexed_result_propagates_errors() {
+    let grid = vec![vec!["a".to_string(), "b".to_string()]];
+    let result = grid.apply_indexed_result(|(_, col), s| {
+        let hex = if col == 0 { "#FF0000" } else { "#nope" };
+        color(hex, s)
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_stripe_rows_grid() {
+    let grid = vec![vec!["a"], vec!["b"], vec!["c"]];
+    let zebra = stripe_rows(Style::new(), Style::new().bg(Color::Ansi256(236)), &grid);
+    assert_eq!(
+        zebra,
+        vec![vec!["a"], vec!["\x1b[48;5;236mb\x1b[0m"], vec!["c"]]
+    );
+}
+
+#[test]
+fn test_style_row_and_column() {
+    let grid = vec![
+        vec!["id".to_string(), "name".to_string()],
+        vec!["1".to_string(), "ada".to_string()],
+    ];
+    let header = style_row(0, Style::new().bold(), &grid);
+    assert_eq!(header[0], vec!["\x1b[1mid\x1b[0m", "\x1b[1mname\x1b[0m"]);
+    assert_eq!(header[1], vec!["1", "ada"]);
+
+    let names = style_column(1, Style::new().italic(), &grid);
+    assert_eq!(names[0], vec!["id", "\x1b[3mname\x1b[0m"]);
+    assert_eq!(names[1], vec!["1", "\x1b[3mada\x1b[0m"]);
+}
+
+#[test]
+fn test_highlight_literal_matches() {
+    let hl = Style::new().bold();
+    assert_eq!(
+        highlight("ab ab", "ab", hl),
+        "\x1b[1mab\x1b[0m \x1b[1mab\x1b[0m"
+    );
+    assert_eq!(highlight("nothing here", "zzz", hl), "nothing here");
+    assert_eq!(highlight("empty needle", "", hl), "empty needle");
+    assert_eq!(highlight("café au lait", "é", hl), "caf\x1b[1mé\x1b[0m au lait");
+}
+
+#[test]
+fn test_highlight_across_existing_escapes() {
+    let text = format!("{}{}", color_ansi(196u8, "ne").unwrap(), "edle");
+    let styled = highlight(text, "needle", Style::new().underline());
+    assert_eq!(styled, "\x1b[4;38;5;196mne\x1b[39medle\x1b[0m");
+}
+
+#[test]
+fn test_highlight_keeps_surrounding_styles() {
+    let text = color_native("green", "ok: 3 passed").unwrap();
+    let styled = highlight(&text, |c: char| c.is_ascii_digit(), Style::new().bold());
+    assert_eq!(styled, "\x1b[32mok: \x1b[1m3\x1b[22m passed\x1b[0m");
+}
+
+#[test]
+fn test_highlight_color_overrides_color() {
+    let text = color_native("red", "a b").unwrap();
+    let styled = highlight(text, 'b', Style::new().fg(Color::Ansi16(3)));
+    assert_eq!(styled, "\x1b[31ma \x1b[33mb\x1b[0m");
+}
+
+#[test]
+fn test_highlight_grid_and_char_sets() {
+    let grid = vec![vec!["a-b", "c"], vec!["d_e"]];
+    let styled = highlight(&grid, ['-', '_'], Style::new().faint());
+    assert_eq!(
+        styled,
+        vec![
+            vec!["a\x1b[2m-\x1b[0mb", "c"],
+            vec!["d\x1b[2m_\x1b[0me"]
+        ]
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_highlight_regex() {
+    let numbers = regex::Regex::new(r"\d+").unwrap();
+    let styled = highlight("took 120ms (p99 340ms)", &numbers, Style::new().bold());
+    assert_eq!(
+        styled,
+        "took \x1b[1m120\x1b[0mms (p\x1b[1m99\x1b[0m \x1b[1m340\x1b[0mms)"
+    );
+}
+
+#[test]
+fn test_style_range_chars() {
+    let bold = Style::new().bold();
+    assert_eq!(style_range("abcdef", 1..3, bold), "a\x1b[1mbc\x1b[0mdef");
+    assert_eq!(style_range("abcdef", 4.., bold), "abcd\x1b[1mef\x1b[0m");
+    assert_eq!(style_range("abcdef", ..=0, bold), "\x1b[1ma\x1b[0mbcdef");
+    assert_eq!(style_range("abc", 5..9, bold), "abc");
+    assert_eq!(style_range("héllo", 1..2, bold), "h\x1b[1mé\x1b[0mllo");
+}
+
+#[test]
+fn test_style_range_bytes() {
+    // "é" takes two bytes, so "llo" starts at byte 3
+    let styled = style_range("héllo", TextRange::bytes(3..6), Style::new().underline());
+    assert_eq!(styled, "hé\x1b[4mllo\x1b[0m");
+}
+
+#[test]
+fn test_style_range_columns() {
+    let inverse = Style::new().inverse();
+    assert_eq!(
+        style_range("a日b", TextRange::columns(2..3), inverse),
+        "a\x1b[7m日\x1b[0mb"
+    );
+    assert_eq!(
+        style_range("a日b", TextRange::columns(3..4), inverse),
+        "a日\x1b[7mb\x1b[0m"
+    );
+    // The combining accent stays with its base letter
+    assert_eq!(
+        style_range("e\u{301}x", TextRange::columns(0..1), inverse),
+        "\x1b[7me\u{301}\x1b[0mx"
+    );
+}
+
+#[test]
+fn test_style_range_keeps_existing_styles() {
+    let text = color_native("red", "error").unwrap();
+    let styled = style_range(text, 1..4, Style::new().bold());
+    assert_eq!(styled, "\x1b[31me\x1b[1mrro\x1b[22mr\x1b[0m");
+}
+
+#[test]
+fn test_style_ranges_later_ranges_take_priority() {
+    let red = Style::new().fg(Color::Ansi16(1));
+    let blue_bold = Style::new().bold().fg(Color::Ansi16(4));
+    let styled = style_ranges("abcd", &[(0..3, red), (1..2, blue_bold)]);
+    assert_eq!(styled, "\x1b[31ma\x1b[1;34mb\x1b[0;31mc\x1b[0md");
+
+    let reversed = style_ranges("abcd", &[(1..2, blue_bold), (0..3, red)]);
+    assert_eq!(reversed, "\x1b[31ma\x1b[1mb\x1b[22mc\x1b[0md");
+}
+
+#[test]
+fn test_style_ranges_vec_and_empty() {
+    let lines = vec!["one", "two"];
+    let styled = style_ranges(&lines, &[(TextRange::chars(..1), Style::new().faint())]);
+    assert_eq!(styled, vec!["\x1b[2mo\x1b[0mne", "\x1b[2mt\x1b[0mwo"]);
+
+    let none: &[(TextRange, Style)] = &[];
+    assert_eq!(style_ranges("plain", none), "plain");
+}
+
+#[test]
+fn test_visible_width() {
+    assert_eq!(visible_width(""), 0);
+    assert_eq!(visible_width("abc"), 3);
+    assert_eq!(visible_width(&color_rgb("#FF0000", "abc").unwrap()), 3);
+    assert_eq!(visible_width("日本語"), 6);
+    assert_eq!(visible_width("e\u{301}"), 1);
+    assert_eq!(visible_width("\x1b]8;;https://example.com\x07link\x1b]8;;\x07"), 4);
+}
+
+#[test]
+fn test_unterminated_csi_before_non_ascii() {
+    // The escape ends at the first byte that can't belong to it, so é stays whole
+    assert_eq!(visible_width("a\x1b[é"), 2);
+    assert_eq!(visible_width("\x1b[12;é日"), 3);
+    let styled = per_char("a\x1b[é", |_, _| Style::new().bold());
+    assert_eq!(styled, "\x1b[1ma\x1b[é\x1b[0m");
+}
+
+#[test]
+fn test_per_char_merges_identical_styles() {
+    let red = Style::new().fg(Color::Ansi16(1));
+    let styled = per_char("aaabbb", |_, c| if c == 'a' { red } else { Style::new().bold() });
+    assert_eq!(styled, "\x1b[31maaa\x1b[0;1mbbb\x1b[0m");
+
+    let same = per_char("same", |_, _| red);
+    assert_eq!(same, red.paint("same"));
+}
+
+#[test]
+fn test_per_char_indexes_visible_characters() {
+    let text = format!("{}c", bold("ab"));
+    let styled = per_char(&text, |i, _| {
+        if i == 2 {
+            Style::new().underline()
+        } else {
+            Style::new()
+        }
+    });
+    assert_eq!(styled, "\x1b[1mab\x1b[0;4mc\x1b[0m");
+}
+
+#[test]
+fn test_per_char_on_vectors() {
+    let lines = vec!["ab", "cd"];
+    let styled = per_char(&lines, |i, _| {
+        if i == 0 {
+            Style::new().bold()
+        } else {
+            Style::new()
+        }
+    });
+    assert_eq!(styled, vec!["\x1b[1ma\x1b[0mb", "\x1b[1mc\x1b[0md"]);
+}
+
+#[test]
+fn test_rainbow_and_hue_cycle() {
+    assert_eq!(rainbow(""), "");
+    assert_eq!(rainbow("a"), "\x1b[38;2;255;0;0ma\x1b[0m");
+    assert_eq!(
+        rainbow(vec!["ab", "c"]),
+        vec![
+            "\x1b[38;2;255;0;0ma\x1b[38;2;0;255;255mb\x1b[0m",
+            "\x1b[38;2;255;0;0mc\x1b[0m"
+        ]
+    );
+    // A zero step keeps one color, emitted once
+    assert_eq!(hue_cycle("abc", 240.0, 0.0), "\x1b[38;2;0;0;255mabc\x1b[0m");
+    assert_eq!(
+        hue_cycle("ab", 0.0, -120.0),
+        "\x1b[38;2;255;0;0ma\x1b[38;2;0;0;255mb\x1b[0m"
+    );
+}
+
+#[test]
+fn test_render_grid_merges_runs() {
+    let blue = background_rgb([0, 0, 255], " ").unwrap();
+    let grid = vec![vec![blue.clone(); 4]; 2];
+    let rendered = render_grid(&grid);
+    assert_eq!(
+        rendered,
+        "\x1b[48;2;0;0;255m    \x1b[0m\n\x1b[48;2;0;0;255m    \x1b[0m"
+    );
+    assert!(rendered.len() < grid.concat().concat().len());
+}
+
+#[test]
+fn test_render_grid_minimal_transitions() {
+    let grid = [
+        ["a".to_string(), bold("b"), bold(color_native("red", "c").unwrap())],
+        ["d".to_string(), "e".to_string(), "f".to_string()],
+    ];
+    assert_eq!(
+        render_grid(&grid),
+        "a\x1b[1mb\x1b[31mc\x1b[0m\ndef"
+    );
+}
+
+#[test]
+fn test_render_grid_matches_naive_output() {
+    // Unterminated styles carry over to the next cell, as they would when printed
+    let grid = vec![vec!["\x1b[4mx", "y"], vec![]];
+    assert_eq!(render_grid(&grid), "\x1b[4mxy\x1b[0m\n");
+    let empty: Vec<Vec<String>> = Vec::new();
+    assert_eq!(render_grid(&empty), "");
+}
+
+#[test]
+fn test_styled_writer_emits_minimal_diffs() {
+    let red = Style::new().fg(Color::Ansi16(1));
+    let mut writer = StyledWriter::new(Vec::new());
+    writer.write_styled(&red, "a").unwrap();
+    writer.write_styled(&red, "b").unwrap();
+    writer.write_styled(&red.underline(), "c").unwrap();
+    writer.write_styled(&Style::new().fg(Color::Ansi16(2)), "d").unwrap();
+    assert_eq!(writer.style(), Style::new().fg(Color::Ansi16(2)));
+    writer.write_styled(&Style::new(), "e").unwrap();
+    assert_eq!(
+        String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+        "\x1b[31mab\x1b[4mc\x1b[0;32md\x1b[0me"
+    );
+
+    // Inline styling at the end of a span is reset directly, without first
+    // switching back to the span's style
+    let mut writer = StyledWriter::new(Vec::new());
+    writer.write_styled(&red, "a\x1b[0mb\x1b[1mc").unwrap();
+    assert_eq!(writer.style(), red);
+    assert_eq!(
+        String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+        "\x1b[31mab\x1b[1mc\x1b[0m"
+    );
+
+    // Anything else written next is in the span's style again
+    let mut writer = StyledWriter::new(Vec::new());
+    writer.write_styled(&red, "a\x1b[1mb").unwrap();
+    write!(writer, "c").unwrap();
+    assert_eq!(
+        String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+        "\x1b[31ma\x1b[1mb\x1b[22mc\x1b[0m"
+    );
+}
+
+#[test]
+fn test_styled_writer_resets_on_drop() {
+    let mut out = Vec::new();
+    {
+        let mut writer = StyledWriter::new(&mut out);
+        writer.set_style(&Style::new().bold()).unwrap();
+        write!(writer, "{} items", 3).unwrap();
+    }
+    assert_eq!(out, b"\x1b[1m3 items\x1b[0m");
+
+    let mut plain = Vec::new();
+    StyledWriter::new(&mut plain).write_styled(&Style::new(), "x").unwrap();
+    assert_eq!(plain, b"x");
+}
+
+#[test]
+fn test_styled_writer_keeps_embedded_styles() {
+    let mut writer = StyledWriter::new(Vec::new());
+    let base = Style::new().fg(Color::Ansi16(4));
+    writer.write_styled(&base, &format!("a{}c", bold("b"))).unwrap();
+    writer.write_styled(&base, "d").unwrap();
+    assert_eq!(
+        writer.into_inner().unwrap(),
+        b"\x1b[34ma\x1b[1mb\x1b[22mcd\x1b[0m"
+    );
+}
+
+#[test]
+fn test_join_horizontal_pads_by_visible_width() {
+    let left = vec![color_rgb("#FF0000", "red").unwrap(), "x".to_string()];
+    let right = vec!["1".to_string(), "2".to_string(), "3".to_string()];
+    let joined = join_horizontal(&[left.clone(), right.clone()], VAlign::Top);
+    assert_eq!(
+        joined,
+        vec![
+            "\x1b[38;2;255;0;0mred\x1b[0m1".to_string(),
+            "x  2".to_string(),
+            "   3".to_string()
+        ]
+    );
+
+    let middle = join_horizontal(&[vec!["a"], vec!["1", "2", "3"]], VAlign::Middle);
+    assert_eq!(middle, vec![" 1", "a2", " 3"]);
+
+    let empty: Vec<Vec<String>> = Vec::new();
+    assert!(join_horizontal(&empty, VAlign::Top).is_empty());
+}
+
+#[test]
+fn test_join_vertical_alignment() {
+    let blocks = [vec!["abcd"], vec!["ab", "日"]];
+    assert_eq!(
+        join_vertical(&blocks, HAlign::Left),
+        vec!["abcd", "ab  ", "日  "]
+    );
+    assert_eq!(
+        join_vertical(&blocks, HAlign::Right),
+        vec!["abcd", "  ab", "  日"]
+    );
+    assert_eq!(
+        join_vertical(&[vec![bold("a")], vec!["abcd".to_string()]], HAlign::Center),
+        vec![" \x1b[1ma\x1b[0m  ", "abcd"]
+    );
+}
+
+#[test]
+fn test_place_pads_and_crops() {
+    assert_eq!(
+        place(&["ab"], 4, 3, HAlign::Center, VAlign::Bottom),
+        vec!["    ", "    ", " ab "]
+    );
+    assert_eq!(
+        place(&[bold("abcdef")], 3, 1, HAlign::Left, VAlign::Top),
+        vec!["\x1b[1mabc\x1b[0m"]
+    );
+    // A wide character that only half fits is left out
+    assert_eq!(
+        place(&["a日"], 2, 1, HAlign::Left, VAlign::Top),
+        vec!["a "]
+    );
+    let none: [&str; 0] = [];
+    assert_eq!(place(&none, 2, 1, HAlign::Left, VAlign::Middle), vec!["  "]);
+}
+
+#[test]
+fn test_layout_splits_embedded_newlines() {
+    assert_eq!(
+        join_horizontal(&["a\nbc", "x"], VAlign::Bottom),
+        vec!["a  ", "bcx"]
+    );
+    assert_eq!(
+        join_vertical(&[vec!["abc"], vec!["a\nb"]], HAlign::Right),
+        vec!["abc", "  a", "  b"]
+    );
+    assert_eq!(
+        place("ab\nc", 3, 3, HAlign::Left, VAlign::Middle),
+        vec!["ab ", "c  ", "   "]
+    );
+    assert_eq!(
+        place(&[bold("a\nb")], 1, 2, HAlign::Left, VAlign::Top),
+        place("\x1b[1ma\nb\x1b[0m", 1, 2, HAlign::Left, VAlign::Top)
+    );
+}
+
+#[test]
+fn test_boxed_border_kinds() {
+    let cases = [
+        (Border::Single, ["┌──┐", "│ab│", "└──┘"]),
+        (Border::Double, ["╔══╗", "║ab║", "╚══╝"]),
+        (Border::Rounded, ["╭──╮", "│ab│", "╰──╯"]),
+        (Border::Heavy, ["┏━━┓", "┃ab┃", "┗━━┛"]),
+        (Border::Ascii, ["+--+", "|ab|", "+--+"]),
+        (Border::Custom(BorderChars::new(['*'; 11])), ["****", "*ab*", "****"]),
+    ];
+
+    for (border, expected) in cases {
+        assert_eq!(boxed("ab", &BoxStyle::new().border(border)), expected);
+    }
+    assert_eq!(boxed("ab", &BoxStyle::new().border(Border::None)), vec!["ab"]);
+}
+
+#[test]
+fn test_boxed_lines_use_visible_width() {
+    let content = vec![color_rgb("#FF0000", "red").unwrap(), "a\nlonger".to_string()];
+    assert_eq!(
+        boxed(&content, &BoxStyle::new().border(Border::Ascii)),
+        vec![
+            "+------+",
+            "|\x1b[38;2;255;0;0mred\x1b[0m   |",
+            "|a     |",
+            "|longer|",
+            "+------+",
+        ]
+    );
+}
+
+#[test]
+fn test_boxed_titles() {
+    let style = BoxStyle::new().border(Border::Ascii);
+    assert_eq!(
+        boxed("0123456789", &style.clone().title("T", HAlign::Center))[0],
+        "+--- T ----+"
+    );
+    assert_eq!(
+        boxed("0123456789", &style.clone().title("T", HAlign::Right))[0],
+        "+------ T -+"
+    );
+    // The box grows to fit a long title
+    assert_eq!(
+        boxed("a", &style.title(bold("Title"), HAlign::Left)),
+        vec![
+            "+- \x1b[1mTitle\x1b[0m -+",
+            "|a        |",
+            "+---------+",
+        ]
+    );
+}
+
+#[test]
+fn test_boxed_colors() {
+    let style = BoxStyle::new()
+        .border(Border::Ascii)
+        .border_style(Style::new().fg(Color::Ansi16(4)));
+    assert_eq!(
+        boxed("a", &style),
+        vec![
+            "\x1b[34m+-+\x1b[0m",
+            "\x1b[34m|\x1b[0ma\x1b[34m|\x1b[0m",
+            "\x1b[34m+-+\x1b[0m",
+        ]
+    );
+
+    let filled = boxed(&vec![bold("a")], &style.background(Color::Ansi16(0)));
+    assert_eq!(filled[1], "\x1b[34;40m|\x1b[1;39ma\x1b[22;34m|\x1b[0m");
+}
+
+#[test]
+fn test_boxed_padding_and_margin() {
+    let style = BoxStyle::new()
+        .border(Border::Ascii)
+        .padding(Spacing::symmetric(0, 1))
+        .margin(Spacing::new(1, 0, 0, 2));
+    assert_eq!(boxed("a", &style), vec!["       ", "  +---+", "  | a |", "  +---+"]);
+}
+
+#[test]
+fn test_table_unicode_with_header() {
+    let rows = vec![
+        vec![bold("id"), "name".to_string()],
+        vec!["1".to_string(), color_native("green", "ada").unwrap()],
+    ];
+    let lines = table(&rows, &Table::new().header(Style::new().underline()));
+    assert_eq!(
+        lines,
+        vec![
+            "┌────┬──────┐",
+            "│\x1b[4m \x1b[1mid\x1b[22m \x1b[0m│\x1b[4m name \x1b[0m│",
+            "├────┼──────┤",
+            "│ 1  │ \x1b[32mada\x1b[0m  │",
+            "└────┴──────┘",
+        ]
+    );
+}
+
+#[test]
+fn test_table_ragged_and_multiline_rows() {
+    let rows = vec![vec!["a", "b\nc"], vec!["d"]];
+    assert_eq!(
+        table(&rows, &Table::new().border(Border::Ascii)),
+        vec!["+---+---+", "| a | b |", "|   | c |", "| d |   |", "+---+---+"]
+    );
+    let empty: Vec<Vec<&str>> = Vec::new();
+    assert!(table(&empty, &Table::new()).is_empty());
+}
+
+#[test]
+fn test_table_max_width_wrap_and_truncate() {
+    let rows = [["the quick brown fox", "x"]];
+    let wrapped = Table::new().border(Border::None).max_width(0, 9);
+    assert_eq!(
+        table(&rows, &wrapped),
+        vec!["the quick  x", "brown fox   "]
+    );
+
+    let truncated = wrapped.clone().overflow(Overflow::Truncate);
+    assert_eq!(table(&rows, &truncated), vec!["the quic…  x"]);
+
+    let long_word = Table::new().border(Border::None).max_width(0, 4);
+    assert_eq!(table(&[["abcdefghij"]], &long_word), vec!["abcd", "efgh", "ij  "]);
+
+    let styled = [[bold("one two")]];
+    assert_eq!(
+        table(&styled, &Table::new().border(Border::None).max_width(0, 3)),
+        vec!["\x1b[1mone\x1b[0m", "\x1b[1mtwo\x1b[0m"]
+    );
+}
+
+#[test]
+fn test_table_row_backgrounds() {
+    let rows = [["h"], ["a"], ["b"], ["c"]];
+    let options = Table::new()
+        .border(Border::None)
+        .padding(0)
+        .header(Style::new().bold())
+        .row_backgrounds([Color::Default, Color::Ansi16(0)]);
+    assert_eq!(
+        table(&rows, &options),
+        vec!["\x1b[1mh\x1b[0m", "a", "\x1b[40mb\x1b[0m", "c"]
+    );
+}
+
+#[test]
+fn test_table_markdown() {
+    let rows = [["left", "mid", "right"], ["a", "b", "c"]];
+    let options = Table::new()
+        .border(TableBorder::Markdown)
+        .align(1, HAlign::Center)
+        .align(2, HAlign::Right);
+    assert_eq!(
+        table(&rows, &options),
+        vec![
+            "| left | mid | right |",
+            "|------|:---:|------:|",
+            "| a    |  b  |     c |",
+        ]
+    );
+}
+
+#[test]
+fn test_table_markdown_escapes_cells() {
+    let rows = [["a|b", "c"], ["d", "e\nf"]];
+    let markdown = Table::new().border(TableBorder::Markdown);
+    assert_eq!(
+        table(&rows, &markdown),
+        vec!["| a\\|b | c      |", "|------|--------|", "| d    | e<br>f |"]
+    );
+
+    // Wrapped lines are joined the same way
+    let wrapped = markdown.max_width(0, 3).overflow(Overflow::Wrap);
+    assert_eq!(
+        table(&[["x"], ["one two"]], &wrapped),
+        vec!["| x          |", "|------------|", "| one<br>two |"]
+    );
+}
+
+#[test]
+fn test_table_border_style() {
+    let options = Table::new()
+        .border(Border::Ascii)
+        .border_style(Style::new().faint())
+        .padding(0);
+    assert_eq!(
+        table(&[["a", "b"]], &options),
+        vec![
+            "\x1b[2m+-+-+\x1b[0m",
+            "\x1b[2m|\x1b[0ma\x1b[2m|\x1b[0mb\x1b[2m|\x1b[0m",
+            "\x1b[2m+-+-+\x1b[0m",
+        ]
+    );
+}
+
+fn sample_tree() -> Node {
+    Node::new("root")
+        .child(
+            Node::new("a")
+                .child(Node::new("a1"))
+                .child(Node::new("a2").child(Node::new("deep"))),
+        )
+        .child(Node::new("b"))
+}
+
+#[test]
+fn test_tree_guides() {
+    assert_eq!(
+        tree(&sample_tree(), &TreeStyle::new()),
+        vec![
+            "root",
+            "├── a",
+            "│   ├── a1",
+            "│   └── a2",
+            "│       └── deep",
+            "└── b",
+        ]
+    );
+    assert_eq!(
+        tree(&sample_tree(), &TreeStyle::new().guides(TreeGuides::ASCII).max_depth(1)),
+        vec!["root", "|-- a", "`-- b"]
+    );
+    assert_eq!(tree(&Node::new("alone"), &TreeStyle::new()), vec!["alone"]);
+}
+
+#[test]
+fn test_tree_styles() {
+    let root = Node::new("d").child(Node::new(italic("f")));
+    let options = TreeStyle::new()
+        .guide_style(Style::new().faint())
+        .branch_style(Style::new().bold())
+        .leaf_style(Style::new().fg(Color::Ansi16(2)));
+    assert_eq!(
+        tree(&root, &options),
+        vec![
+            "\x1b[1md\x1b[0m",
+            "\x1b[2m└── \x1b[0m\x1b[3;32mf\x1b[0m",
+        ]
+    );
+}
+
+#[test]
+fn test_tree_multiline_labels() {
+    let root = Node::new("r")
+        .child(Node::new("two\nlines").child(Node::new("c")))
+        .child(Node::new("z"));
+    assert_eq!(
+        tree(&root, &TreeStyle::new()),
+        vec!["r", "├── two", "│   lines", "│   └── c", "└── z"]
+    );
+}
+
+/// A directory whose entries are a linked list of siblings, not a slice.
+struct Dir {
+    name: &'static str,
+    first: Option<Box<Dir>>,
+    next: Option<Box<Dir>>,
+}
+
+impl Dir {
+    fn new(name: &'static str, entries: Vec<Dir>) -> Dir {
+        let first = entries.into_iter().rev().fold(None, |next, mut entry| {
+            entry.next = next;
+            Some(Box::new(entry))
+        });
+        Dir { name, first, next: None }
+    }
+}
+
+impl TreeNode for Dir {
+    fn label(&self) -> &str {
+        self.name
+    }
+
+    fn children(&self) -> impl Iterator<Item = &Self> {
+        std::iter::successors(self.first.as_deref(), |entry| entry.next.as_deref())
+    }
+}
+
+#[test]
+fn test_tree_custom_node() {
+    let dir = Dir::new(
+        "/",
+        vec![Dir::new("etc", vec![Dir::new("hosts", vec![])]), Dir::new("usr", vec![])],
+    );
+    assert_eq!(
+        tree(&dir, &TreeStyle::new()),
+        vec!["/", "├── etc", "│   └── hosts", "└── usr"]
+    );
+}
+
+#[test]
+fn test_progress_eighths() {
+    let bar = ProgressBar::new(2);
+    assert_eq!(bar.render(0.0), "  ");
+    assert_eq!(bar.render(1.0 / 16.0), "▏ ");
+    assert_eq!(bar.render(0.5), "█ ");
+    assert_eq!(bar.render(0.5 + 7.0 / 16.0), "█▉");
+    assert_eq!(bar.render(1.0), "██");
+    assert_eq!(bar.render(2.0), "██");
+    assert_eq!(bar.render(f64::NAN), "  ");
+}
+
+#[test]
+fn test_progress_fill_and_track() {
+    let bar = ProgressBar::new(4)
+        .fill([0, 255, 0])
+        .track('·', Style::new().faint());
+    assert_eq!(
+        bar.render(0.375),
+        "\x1b[38;2;0;255;0m█\x1b[2m▌\x1b[39m··\x1b[0m"
+    );
+}
+
+#[test]
+fn test_progress_gradient() {
+    let gradient = Gradient::new(["#FF0000", "#0000FF"]).unwrap();
+    let bar = ProgressBar::new(2).fill(gradient.clone());
+    assert_eq!(
+        bar.render(1.0),
+        "\x1b[38;2;191;0;64m█\x1b[38;2;64;0;191m█\x1b[0m"
+    );
+
+    // Neighboring cells that map to the same palette color share one escape
+    let bar = ProgressBar::new(3).fill(gradient).color_depth(ColorDepth::Ansi256);
+    let rendered = bar.render(1.0);
+    assert_eq!(visible_width(&rendered), 3);
+    assert!(rendered.starts_with("\x1b[38;5;"));
+    assert!(rendered.matches("\x1b[38;5;").count() <= 3);
+}
+
+#[test]
+fn test_progress_fields() {
+    let bar = ProgressBar::new(4).label(bold("Build")).percentage(true);
+    assert_eq!(bar.render(0.5), "\x1b[1mBuild\x1b[0m ██    50%");
+    assert_eq!(bar.render(0.999), "\x1b[1mBuild\x1b[0m ████  99%");
+    assert_eq!(bar.render(1.0), "\x1b[1mBuild\x1b[0m ████ 100%");
+}
+
+#[test]
+fn test_progress_eta() {
+    let bar = ProgressBar::new(2);
+    assert_eq!(bar.render_with_eta(0.0, Duration::from_secs(5)), "   ETA --:--");
+    assert_eq!(bar.render_with_eta(0.5, Duration::from_secs(75)), "█  ETA 1:15");
+    assert_eq!(
+        bar.render_with_eta(0.01, Duration::from_secs(60)),
+        "   ETA 1:39:00"
+    );
+    assert_eq!(bar.render_with_eta(1.0, Duration::from_secs(60)), "██ ETA 0:00");
+}
+
+#[test]
+fn test_redraw() {
+    let mut out = Vec::new();
+    redraw(&mut out, "50%").unwrap();
+    redraw(&mut out, "60%").unwrap();
+    assert_eq!(out, b"\r50%\x1b[K\r60%\x1b[K");
+}
+
+#[test]
+fn test_spinner_frames() {
+    let mut spinner = Spinner::new(Frames::ARC);
+    let frames: Vec<String> = spinner.by_ref().take(7).collect();
+    assert_eq!(frames, vec!["◜", "◠", "◝", "◞", "◡", "◟", "◜"]);
+    assert_eq!(spinner.next_frame(), "◠");
+
+    for frames in [Frames::DOTS, Frames::LINE, Frames::ARC, Frames::BRAILLE] {
+        assert!(!frames.is_empty());
+        assert!(frames.as_slice().iter().all(|frame| visible_width(frame) == 1));
+    }
+    assert_eq!(Spinner::new(Frames::new(&[])).frame(3), "");
+}
+
+#[test]
+fn test_spinner_colors() {
+    let spinner = Spinner::new(Frames::LINE)
+        .style(Style::new().bold().fg(Color::Ansi16(1)))
+        .colors(ColorCycle::hue(120.0, 3));
+    assert_eq!(spinner.frame(0), "\x1b[1;38;2;0;255;0m-\x1b[0m");
+    assert_eq!(spinner.frame(1), "\x1b[1;38;2;0;0;255m\\\x1b[0m");
+    assert_eq!(spinner.frame(3), "\x1b[1;38;2;0;255;0m/\x1b[0m");
+}
+
+#[test]
+fn test_color_cycle_pulse() {
+    let pulse = ColorCycle::pulse([200, 40, 40], 8).unwrap();
+    assert_eq!(pulse.color_at(0), Color::Rgb([200, 40, 40]));
+    assert_eq!(pulse.color_at(8), Color::Rgb([200, 40, 40]));
+    // Symmetric around the dimmest point, halfway through
+    assert_eq!(pulse.color_at(3), pulse.color_at(5));
+    assert_eq!(pulse.color_at(4), Color::Rgb([80, 16, 16]));
+
+    // Eased: the change is slowest next to the brightest point
+    let red = |tick| match pulse.color_at(tick) {
+        Color::Rgb([r, _, _]) => r as i32,
+        other => panic!("unexpected {:?}", other),
+    };
+    assert!(red(0) - red(1) < red(1) - red(2));
+
+    let palette = pulse.clone().color_depth(ColorDepth::Ansi256);
+    assert!(matches!(palette.color_at(2), Color::Ansi256(_)));
+    assert!(ColorCycle::pulse("zzz", 8).is_err());
+}
+
+#[test]
+fn test_color_cycle_gradient() {
+    let gradient = Gradient::new(["#000000", "#FFFFFF"]).unwrap();
+    let cycle = ColorCycle::gradient(gradient, 4);
+    assert_eq!(cycle.color_at(0), Color::Rgb([0, 0, 0]));
+    assert!(matches!(cycle.color_at(1), Color::Rgb([127..=128, 127..=128, 127..=128])));
+    assert_eq!(cycle.color_at(2), Color::Rgb([255, 255, 255]));
+    assert_eq!(cycle.color_at(3), cycle.color_at(1));
+
+    // A zero period does not divide by zero
+    assert_eq!(ColorCycle::hue(0.0, 0).color_at(5), Color::Rgb([255, 0, 0]));
+}
+
+#[test]
+fn test_ticker() {
+    let ticker = Ticker::new(Duration::from_millis(100));
+    assert_eq!(ticker.tick_at(Duration::ZERO), 0);
+    assert_eq!(ticker.tick_at(Duration::from_millis(99)), 0);
+    assert_eq!(ticker.tick_at(Duration::from_secs(1)), 10);
+    assert_eq!(Ticker::new(Duration::ZERO).tick_at(Duration::from_nanos(3)), 3);
+}
+
+#[test]
+fn test_sparkline_levels() {
+    assert_eq!(sparkline(&[]), "");
+    assert_eq!(sparkline(&[3.0, 1.0, 2.0]), "█▁▅");
+    assert_eq!(sparkline(&[5.0, 5.0]), "▅▅");
+    assert_eq!(sparkline(&[f64::NAN, 1.0, f64::INFINITY, 2.0]), " ▁ █");
+    assert_eq!(Sparkline::new().range(0.0, 10.0).render(&[-5.0, 5.0, 20.0]), "▁▅█");
+}
+
+#[test]
+fn test_sparkline_colors() {
+    let spark = Sparkline::new().colors(Gradient::new(["#00FF00", "#FF0000"]).unwrap());
+    assert_eq!(
+        spark.render(&[0.0, 0.0, 1.0]),
+        "\x1b[38;2;0;255;0m▁▁\x1b[38;2;255;0;0m█\x1b[0m"
+    );
+
+    let palette = spark.color_depth(ColorDepth::Ansi256);
+    assert_eq!(
+        palette.render(&[0.0, f64::NAN, 1.0]),
+        "\x1b[38;5;46m▁\x1b[0m \x1b[38;5;196m█\x1b[0m"
+    );
+}
+
+#[test]
+fn test_bar_chart_scaling() {
+    let chart = bar_chart(&[("a", 4.0), ("bb", 1.0), ("c", -2.0), ("d", f64::NAN)], 4);
+    assert_eq!(chart, vec!["a  ████ 4", "bb █    1", "c       -2", "d       NaN"]);
+
+    let fine = BarChart::new().values(false).render(&[("x", 1.0), ("y", 0.3)], 2);
+    assert_eq!(fine, vec!["x ██", "y ▋"]);
+
+    let fixed = BarChart::new().max(10.0).precision(2).render(&[("x", 5.0), ("y", 25.0)], 2);
+    assert_eq!(fixed, vec!["x █  5.00", "y ██ 25.00"]);
+}
+
+#[test]
+fn test_bar_chart_labels_and_colors() {
+    let chart = BarChart::new()
+        .colors(Gradient::new(["#0000FF", "#FF0000"]).unwrap())
+        .render(&[(bold("hot"), 2.0), ("cold".to_string(), 1.0)], 2);
+    assert_eq!(
+        chart,
+        vec![
+            "\x1b[1mhot\x1b[0m  \x1b[38;2;255;0;0m██\x1b[0m 2",
+            "cold \x1b[38;2;128;0;128m█\x1b[0m  1",
+        ]
+    );
+    assert!(bar_chart::<&str>(&[], 10).is_empty());
+}
+
+#[test]
+fn test_canvas_pixels() {
+    let mut canvas = Canvas::new(3, 2, CanvasMode::Braille);
+    assert_eq!((canvas.width(), canvas.height()), (6, 8));
+
+    let red = Color::Ansi16(1);
+    canvas.set(1, 1, red);
+    canvas.set(-1, 0, red);
+    canvas.set(6, 0, red);
+    assert_eq!(canvas.get(1, 1), Some(red));
+    assert_eq!(canvas.get(0, 0), None);
+    assert_eq!(canvas.get(-1, 0), None);
+    assert_eq!(canvas.render(), vec!["\x1b[31m⠐  \x1b[0m", "   "]);
+
+    canvas.unset(1, 1);
+    assert_eq!(canvas.render(), vec!["   ", "   "]);
+    canvas.fill_rect(0, 0, 6, 8, red);
+    canvas.clear();
+    assert_eq!(canvas.get(3, 3), None);
+}
+
+#[test]
+fn test_canvas_braille_dots() {
+    // Every dot of a cell lit maps to the full braille pattern
+    let mut canvas = Canvas::new(1, 1, CanvasMode::Braille);
+    canvas.fill_rect(0, 0, 2, 4, Color::Default);
+    assert_eq!(canvas.render(), vec!["\x1b[39m⣿\x1b[0m"]);
+
+    let mut canvas = Canvas::new(1, 1, CanvasMode::Braille);
+    canvas.set(0, 3, Color::Ansi16(2));
+    canvas.set(1, 0, Color::Ansi16(2));
+    assert_eq!(canvas.render(), vec!["\x1b[32m⡈\x1b[0m"]);
+}
+
+#[test]
+fn test_canvas_half_blocks() {
+    let (red, blue) = (Color::Ansi16(1), Color::Ansi16(4));
+    let mut canvas = Canvas::new(4, 1, CanvasMode::HalfBlock);
+    canvas.set(0, 0, red);
+    canvas.set(1, 1, red);
+    canvas.set(2, 0, red);
+    canvas.set(2, 1, red);
+    canvas.set(3, 0, red);
+    canvas.set(3, 1, blue);
+    assert_eq!(canvas.render(), vec!["\x1b[31m▀▄█\x1b[44m▀\x1b[0m"]);
+}
+
+#[test]
+fn test_canvas_quadrants_and_dominant_color() {
+    let (red, blue) = (Color::Ansi16(1), Color::Ansi16(4));
+    let mut canvas = Canvas::new(2, 1, CanvasMode::Quadrant);
+    canvas.set(0, 0, red);
+    canvas.set(1, 1, blue);
+    canvas.set(0, 1, blue);
+    canvas.set(3, 0, red);
+    assert_eq!(canvas.render(), vec!["\x1b[34m▙\x1b[31m▝\x1b[0m"]);
+}
+
+#[test]
+fn test_canvas_lines() {
+    let white = Color::Ansi16(7);
+    let mut canvas = Canvas::new(5, 5, CanvasMode::HalfBlock);
+
+    // Steep and shallow lines in either direction visit every step once
+    canvas.line(4, 0, 0, 2, white);
+    let lit: Vec<(i32, i32)> = (0..5)
+        .flat_map(|y| (0..5).map(move |x| (x, y)))
+        .filter(|&(x, y)| canvas.get(x, y).is_some())
+        .collect();
+    assert_eq!(lit, vec![(4, 0), (2, 1), (3, 1), (0, 2), (1, 2)]);
+
+    canvas.clear();
+    canvas.line(2, 9, 2, 5, white);
+    assert!((5..=9).all(|y| canvas.get(2, y).is_some()));
+    assert!(canvas.get(2, 4).is_none());
+
+    canvas.clear();
+    canvas.line(-10, 0, 100, 0, white);
+    assert!((0..5).all(|x| canvas.get(x, 0).is_some()));
+}
+
+#[test]
+fn test_canvas_shapes() {
+    let white = Color::Ansi16(7);
+    let mut canvas = Canvas::new(4, 2, CanvasMode::Quadrant);
+    canvas.rect(0, 0, 8, 4, white);
+    assert_eq!(canvas.render(), vec!["\x1b[37m▛▀▀▜\x1b[0m", "\x1b[37m▙▄▄▟\x1b[0m"]);
+
+    canvas.clear();
+    canvas.rect(1, 1, 0, 3, white);
+    assert_eq!(canvas.render(), vec!["    ", "    "]);
+
+    let mut canvas = Canvas::new(7, 7, CanvasMode::HalfBlock);
+    canvas.circle(3, 3, 3, white);
+    let row = |y| (0..7).map(|x| if canvas.get(x, y).is_some() { '#' } else { '.' }).collect::<String>();
+    assert_eq!(
+        (0..7).map(row).collect::<Vec<_>>(),
+        vec!["..###..", ".#...#.", "#.....#", "#.....#", "#.....#", ".#...#.", "..###.."]
+    );
+}
+
+#[test]
+fn test_canvas_extreme_coordinates() {
+    let white = Color::Ansi16(7);
+    let mut canvas = Canvas::new(4, 2, CanvasMode::HalfBlock);
+    let lit = |canvas: &Canvas| {
+        (0..4)
+            .flat_map(|y| (0..4).map(move |x| (x, y)))
+            .filter(|&(x, y)| canvas.get(x, y).is_some())
+            .count()
+    };
+
+    // Only the part of each shape on the canvas is visited, without overflow
+    canvas.line(0, 0, i32::MAX, 0, white);
+    assert_eq!(lit(&canvas), 4);
+    canvas.clear();
+    canvas.line(i32::MIN, i32::MIN, i32::MAX, i32::MAX, white);
+    assert_eq!(lit(&canvas), 4);
+    assert!((0..4).all(|i| canvas.get(i, i).is_some()));
+
+    canvas.clear();
+    canvas.rect(i32::MAX - 1, 0, 10, 10, white);
+    canvas.rect(i32::MIN, i32::MIN, u32::MAX, u32::MAX, white);
+    assert_eq!(lit(&canvas), 0);
+    canvas.rect(-1, -1, 5, u32::MAX, white);
+    assert!((0..4).all(|y| canvas.get(3, y).is_some()));
+    assert_eq!(lit(&canvas), 4);
+
+    canvas.clear();
+    canvas.fill_rect(i32::MIN, i32::MIN, u32::MAX, u32::MAX, white);
+    assert_eq!(lit(&canvas), 16);
+    canvas.clear();
+    canvas.fill_rect(i32::MAX, 0, u32::MAX, 1, white);
+    assert_eq!(lit(&canvas), 0);
+
+    // A huge circle around the canvas misses it, and one through it crosses it
+    canvas.clear();
+    canvas.circle(2, 2, u32::MAX, white);
+    canvas.circle(i32::MIN, 0, u32::MAX, white);
+    assert_eq!(lit(&canvas), 0);
+    canvas.circle(-1_000_000, 2, 1_000_001, white);
+    assert_eq!(lit(&canvas), 4);
+    assert!((0..4).all(|y| canvas.get(1, y).is_some()));
+}
+
+#[test]
+fn test_image_area_average() {
+    // A 4×2 checkerboard of black and white averages to gray at half size
+    let mut pixels = Vec::new();
+    for y in 0..2 {
+        for x in 0..4 {
+            pixels.extend([if (x + y) % 2 == 0 { 255 } else { 0 }; 3]);
+        }
+    }
+    let lines = image::render(4, 2, &pixels, 2).unwrap();
+    assert_eq!(lines, vec!["\x1b[38;2;128;128;128m▀▀\x1b[0m"]);
+}
+
+#[test]
+fn test_image_aspect_ratio() {
+    let pixels = vec![200; 10 * 10 * 3];
+    // Square pixels: 10 columns need 10 pixel rows, or 5 lines
+    assert_eq!(image::render(10, 10, &pixels, 10).unwrap().len(), 5);
+    assert_eq!(image::render(10, 10, &pixels, 4).unwrap().len(), 2);
+
+    // Wider cells need fewer rows to keep the same proportions
+    let wide = ImageOptions::new().cell_aspect(4.0);
+    assert_eq!(wide.render(10, 10, &pixels, 10).unwrap().len(), 3);
+
+    // An odd pixel row leaves the lower half of the last line empty
+    let lines = image::render(10, 10, &pixels, 3).unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1], "\x1b[38;2;200;200;200m▀▀▀\x1b[0m");
+}
+
+#[test]
+fn test_image_upscale_and_ansi() {
+    // A single pixel stretched across a larger area keeps its color
+    let lines = ImageOptions::new()
+        .color_depth(ColorDepth::Ansi256)
+        .render(1, 1, &[255, 0, 0], 3)
+        .unwrap();
+    assert_eq!(
+        lines,
+        vec!["\x1b[38;5;196;48;5;196m▀▀▀\x1b[0m", "\x1b[38;5;196m▀▀▀\x1b[0m"]
+    );
+}
+
+#[test]
+fn test_image_errors() {
+    assert_eq!(
+        image::render(2, 2, &[0; 11], 2),
+        Err(ColorConversionError::InvalidRgb(
+            "expected 12 bytes for a 2x2 image, found 11".to_string()
+        ))
+    );
+    assert_eq!(image::render(0, 0, &[], 10), Ok(vec![]));
+    assert_eq!(image::render(1, 1, &[0, 0, 0], 0), Ok(vec![]));
+
+    // Sizes too large to multiply out are errors, not overflows
+    let huge = format!("a {}x2 image is too large", usize::MAX);
+    assert_eq!(image::render(usize::MAX, 2, &[], 1), Err(ColorConversionError::InvalidRgb(huge)));
+    assert!(sixel::encode(2, usize::MAX, &[]).is_err());
+    let image = ImageData::Rgba { width: usize::MAX / 2, height: 2, pixels: &[] };
+    assert!(kitty::encode(&image).is_err());
+    assert!(iterm2::encode(&image).is_err());
+    // Even an empty image must fit the 32-bit sizes of the protocols
+    let wide = ImageData::Rgb { width: u32::MAX as usize + 1, height: 0, pixels: &[] };
+    let huge = format!("a {}x0 image is too large", u32::MAX as usize + 1);
+    assert_eq!(kitty::encode(&wide), Err(ColorConversionError::InvalidRgb(huge)));
+    assert!(iterm2::encode(&wide).is_err());
+}
+
+#[test]
+fn test_image_extreme_sizes() {
+    // Extreme aspects are clamped rather than scaling to billions of rows
+    let lines = ImageOptions::new().cell_aspect(1e-300).render(1, 1, &[1, 2, 3], 1).unwrap();
+    assert_eq!(lines.len(), 10);
+    let lines = ImageOptions::new().cell_aspect(1e300).render(1, 1, &[1, 2, 3], 1).unwrap();
+    assert_eq!(lines.len(), 1);
+    // Invalid aspects keep the default
+    let default = image::render(2, 2, &[9; 12], 2).unwrap();
+    for aspect in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert_eq!(ImageOptions::new().cell_aspect(aspect).render(2, 2, &[9; 12], 2).unwrap(), default);
+    }
+
+    // Sizes too large to scale to are errors, not allocation failures
+    let huge = format!("a 1x1 image is too large to scale to {} columns", usize::MAX);
+    assert_eq!(image::render(1, 1, &[1, 2, 3], usize::MAX), Err(ColorConversionError::InvalidRgb(huge)));
+    assert!(image::render(1, 1, &[1, 2, 3], 1 << 40).is_err());
+}
+
+#[test]
+fn test_image_dither() {
+    // A horizontal ramp: dithering in the 256-color palette only changes the colors
+    let pixels: Vec<u8> = (0..32 * 8).flat_map(|i| [(i % 32 * 8) as u8, 60, 90]).collect();
+    let plain = ImageOptions::new().color_depth(ColorDepth::Ansi256);
+    let dithered = plain.dither(Dither::FloydSteinberg).render(32, 8, &pixels, 32).unwrap();
+    let banded = plain.render(32, 8, &pixels, 32).unwrap();
+    assert_eq!(dithered.len(), banded.len());
+    assert_ne!(dithered, banded);
+    assert!(dithered.iter().all(|line| visible_width(line) == 32));
+
+    // TrueColor output is never dithered
+    let truecolor = ImageOptions::new().dither(Dither::Bayer).render(32, 8, &pixels, 32).unwrap();
+    assert_eq!(truecolor, image::render(32, 8, &pixels, 32).unwrap());
+}
+
+#[test]
+fn test_sixel_bands_and_runs() {
+    // 5×7 red, with one blue pixel in the top right corner
+    let mut pixels = [[255u8, 0, 0]; 35];
+    pixels[4] = [0, 0, 255];
+    let sixel = sixel::encode(5, 7, &pixels.concat()).unwrap();
+    assert_eq!(
+        sixel,
+        concat!(
+            "\x1bP0;0;0q\"1;1;5;7",
+            "#0;2;100;0;0#1;2;0;0;100",
+            "#0!4~}$#1!4?@",
+            "-#0!5@",
+            "\x1b\\",
+        )
+    );
+}
+
+#[test]
+fn test_sixel_transparent() {
+    let pixels = [255, 0, 0, 0, 0, 0, 255, 0, 0];
+    let sixel = SixelOptions::new().transparent([0, 0, 0]).encode(3, 1, &pixels).unwrap();
+    assert_eq!(sixel, "\x1bP0;1;0q\"1;1;3;1#0;2;100;0;0#0@?@\x1b\\");
+
+    // Without transparency black gets a register like any other color
+    let sixel = sixel::encode(3, 1, &pixels).unwrap();
+    assert_eq!(
+        sixel,
+        "\x1bP0;0;0q\"1;1;3;1#0;2;100;0;0#1;2;0;0;0#0@?@$#1?@\x1b\\"
+    );
+}
+
+#[test]
+fn test_sixel_palette_reduction() {
+    // 300 distinct colors are too many for the color registers
+    let pixels: Vec<u8> = (0..300u32).flat_map(|i| [(i % 256) as u8, (i / 256 * 200) as u8, 50]).collect();
+    let registers = |sixel: &str| sixel.matches(";2;").count();
+
+    let sixel = sixel::encode(300, 1, &pixels).unwrap();
+    assert!(sixel.starts_with("\x1bP0;0;0q\"1;1;300;1#0;2;"));
+    assert!(sixel.ends_with("\x1b\\"));
+    assert!(registers(&sixel) <= 256);
+
+    let sixel = SixelOptions::new()
+        .palette(Palette::ansi16())
+        .dither(Dither::None)
+        .encode(300, 1, &pixels)
+        .unwrap();
+    assert!(registers(&sixel) <= 16);
+}
+
+#[test]
+fn test_sixel_errors() {
+    assert!(matches!(
+        sixel::encode(2, 2, &[0; 3]),
+        Err(ColorConversionError::InvalidRgb(_))
+    ));
+    assert_eq!(sixel::encode(0, 0, &[]).unwrap(), "\x1bP0;0;0q\"1;1;0;0\x1b\\");
+}
+
+#[test]
+fn test_kitty_encode_formats() {
+    let rgb = [255, 0, 0];
+    assert_eq!(
+        kitty::encode(&ImageData::Rgb { width: 1, height: 1, pixels: &rgb }).unwrap(),
+        "\x1b_Ga=T,f=24,s=1,v=1;/wAA\x1b\\"
+    );
+    let png = [0x89, b'P', b'N', b'G'];
+    assert_eq!(
+        kitty::encode(&ImageData::Png(&png)).unwrap(),
+        "\x1b_Ga=T,f=100;iVBORw==\x1b\\"
+    );
+    assert!(matches!(
+        kitty::encode(&ImageData::Rgba { width: 2, height: 1, pixels: &rgb }),
+        Err(ColorConversionError::InvalidRgb(_))
+    ));
+}
+
+#[test]
+fn test_kitty_chunks_payload() {
+    // 3072 pixels of RGB are exactly 12288 base64 bytes: three full chunks
+    let pixels = vec![7u8; 64 * 48 * 3];
+    let image = ImageData::Rgb { width: 64, height: 48, pixels: &pixels };
+    let sequence = KittyImage::new().id(3).quiet(true).encode(&image).unwrap();
+    let chunks: Vec<&str> = sequence.split_terminator("\x1b\\").collect();
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks[0].starts_with("\x1b_Ga=T,f=24,s=64,v=48,i=3,q=2,m=1;"));
+    assert!(chunks[1].starts_with("\x1b_Gm=1,q=2;"));
+    assert!(chunks[2].starts_with("\x1b_Gm=0,q=2;"));
+    for chunk in &chunks {
+        assert_eq!(chunk.split_once(';').unwrap().1.len(), 4096);
+    }
+}
+
+#[test]
+fn test_kitty_zero_id_is_ignored() {
+    let image = ImageData::Rgb { width: 1, height: 1, pixels: &[255, 0, 0] };
+    assert_eq!(KittyImage::new().id(0).encode(&image), kitty::encode(&image));
+    // A later id replaces an earlier one, and 0 clears it
+    assert_eq!(KittyImage::new().id(5).id(0), KittyImage::new());
+}
+
+#[test]
+fn test_kitty_delete() {
+    assert_eq!(kitty::delete(42), "\x1b_Ga=d,d=I,i=42\x1b\\");
+    // Id 0 is no id, so there is nothing to delete
+    assert_eq!(kitty::delete(0), "");
+    assert_eq!(kitty::delete_all(), "\x1b_Ga=d,d=A\x1b\\");
+}
+
+#[test]
+fn test_iterm2_encode_png() {
+    let png = [0x89, b'P', b'N', b'G'];
+    assert_eq!(
+        iterm2::encode(&ImageData::Png(&png)).unwrap(),
+        "\x1b]1337;File=inline=1;size=4;preserveAspectRatio=1:iVBORw==\x07"
+    );
+    assert_eq!(
+        ITermImage::new().cells(8, 2).preserve_aspect_ratio(false).encode(&ImageData::Png(&png)).unwrap(),
+        "\x1b]1337;File=inline=1;size=4;width=8;height=2;preserveAspectRatio=0:iVBORw==\x07"
+    );
+}
+
+#[test]
+fn test_iterm2_encodes_raw_pixels_as_png() {
+    let pixels = [255, 0, 0, 0, 255, 0];
+    let sequence = iterm2::encode(&ImageData::Rgb { width: 2, height: 1, pixels: &pixels }).unwrap();
+    let (header, data) = sequence.trim_end_matches('\x07').split_once(':').unwrap();
+    // Signature, IHDR, IDAT with one stored block of two filtered rows, IEND
+    let size = 8 + 25 + 12 + 2 + 5 + 7 + 4 + 12;
+    assert_eq!(header, format!("\x1b]1337;File=inline=1;size={};preserveAspectRatio=1", size));
+    assert_eq!(data.len(), (size as usize).div_ceil(3) * 4);
+    // The PNG signature and the start of the IHDR chunk
+    assert!(data.starts_with("iVBORw0KGgoAAAANSUhEUgAAAAIAAAAB"));
+    assert!(matches!(
+        iterm2::encode(&ImageData::Rgb { width: 2, height: 2, pixels: &pixels }),
+        Err(ColorConversionError::InvalidRgb(_))
+    ));
+}
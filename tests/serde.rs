@@ -0,0 +1,67 @@
+#![cfg(feature = "serde")]
+
+use terminal_style::color::Color;
+use terminal_style::format::{color_rgb, Style};
+
+#[test]
+fn test_color_serialize() {
+    assert_eq!(serde_json::to_string(&Color::Rgb([255, 20, 147])).unwrap(), "\"#FF1493\"");
+    assert_eq!(serde_json::to_string(&Color::Ansi16(4)).unwrap(), "\"blue\"");
+    assert_eq!(serde_json::to_string(&Color::Ansi256(196)).unwrap(), "\"ansi:196\"");
+}
+
+#[test]
+fn test_color_deserialize_forms() {
+    let colors: Vec<Color> =
+        serde_json::from_str(r##"["#FF1493", "ansi:4", 196, [1, 2, 3], "default"]"##).unwrap();
+    assert_eq!(
+        colors,
+        vec![
+            Color::Rgb([255, 20, 147]),
+            Color::Ansi16(4),
+            Color::Ansi256(196),
+            Color::Rgb([1, 2, 3]),
+            Color::Default,
+        ]
+    );
+}
+
+#[test]
+fn test_color_deserialize_usable_with_color_rgb() {
+    let color: Color = serde_json::from_str("\"#00FF00\"").unwrap();
+    assert_eq!(color_rgb(color, "Go").unwrap(), "\x1b[38;2;0;255;0mGo\x1b[0m");
+}
+
+#[test]
+fn test_color_deserialize_errors_carry_details() {
+    let err = serde_json::from_str::<Color>("\"#12\"").unwrap_err();
+    assert!(err.to_string().contains("Invalid hex string"), "{}", err);
+
+    let err = serde_json::from_str::<Color>("300").unwrap_err();
+    assert!(err.to_string().contains("Invalid ANSI value: 300"), "{}", err);
+
+    let err = serde_json::from_str::<Color>("[1, 2, 300]").unwrap_err();
+    assert!(err.to_string().contains("Invalid RGB value"), "{}", err);
+}
+
+#[test]
+fn test_style_roundtrip() {
+    let style = Style::new().bold().fg(Color::Rgb([255, 20, 147])).bg(Color::Ansi16(0));
+    let json = serde_json::to_string(&style).unwrap();
+    assert_eq!(json, "\"bold #FF1493 on black\"");
+    assert_eq!(serde_json::from_str::<Style>(&json).unwrap(), style);
+}
+
+#[test]
+fn test_style_deserialize_map() {
+    let style: Style =
+        serde_json::from_str(r##"{ "fg": "ansi:208", "bg": [0, 0, 0], "underline": true }"##)
+            .unwrap();
+    assert_eq!(
+        style,
+        Style::new().underline().fg(Color::Ansi256(208)).bg(Color::Rgb([0, 0, 0]))
+    );
+
+    let err = serde_json::from_str::<Style>(r#"{ "colour": "red" }"#).unwrap_err();
+    assert!(err.to_string().contains("unknown field"), "{}", err);
+}